@@ -8,6 +8,10 @@ pub mod enums {
         Help,
         #[command(description = "Shows how to format the query correctly")]
         FormatGuide,
+        #[command(description = "Sets how cut fractions are rounded: halfeven, halfup, truncate, ceiling or floor")]
+        Rounding(String),
+        #[command(description = "Explains a conversion step by step: /explain 13.625_10 > 2")]
+        Explain(String),
     }
 
     #[derive(BotCommands, Clone)]
@@ -25,13 +29,16 @@ pub mod executors {
     use std::error::Error;
     use std::sync::Arc;
     use teloxide::Bot;
+    use teloxide::payloads::SendMessageSetters;
     use teloxide::prelude::{AutoSend, Message, Requester};
+    use teloxide::types::ParseMode;
     use crate::commands::FORMAT_GUIDE_MESSAGE;
     use crate::constants::MAINTAINER_ID;
     use crate::teloxide::utils::command::BotCommands;
     use crate::converter::ValidErrorKind;
     use crate::converter::error_messages;
-    use crate::Journal;
+    use crate::digits::converting::RoundingMode;
+    use crate::{debug, Journal};
 
     type HandlerResult = Result<(), Box<dyn Error + Sync + Send>>;
 
@@ -39,7 +46,7 @@ pub mod executors {
         if message.from().unwrap().id == MAINTAINER_ID {
             bot.send_message(
                 message.chat.id,
-                &format!(
+                format!(
                     "{}\n\n{}",
                     Command::descriptions(),
                     MaintainerCommand::descriptions(),
@@ -74,6 +81,46 @@ pub mod executors {
         Ok(())
     }
 
+    pub async fn rounding(
+        bot: AutoSend<Bot>,
+        message: Message,
+        journal: Arc<Journal>,
+        mode: &str,
+    ) -> HandlerResult {
+        let id = message.from().unwrap().id;
+        let names = RoundingMode::ALL.map(RoundingMode::name).join(", ");
+
+        if mode.is_empty() {
+            let current = journal.rounding_mode(id).unwrap_or_default();
+            bot.send_message(
+                message.chat.id,
+                format!("Rounding mode: {}\nChoose one of: {}", current.name(), names)
+            ).await?;
+        } else if let Some(mode) = RoundingMode::from_name(mode) {
+            let reply = if let Err(error) = journal.set_rounding_mode(id, mode) {
+                debug::print_debug_error(error);
+                "Can't save the rounding mode, try again later".to_owned()
+            } else {
+                format!("Rounding mode is set to {}", mode.name())
+            };
+            bot.send_message(message.chat.id, reply).await?;
+        } else {
+            bot.send_message(
+                message.chat.id,
+                format!("Unknown rounding mode. Choose one of: {}", names)
+            ).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn explanation(bot: AutoSend<Bot>, message: Message, explanation: &str) -> HandlerResult {
+        let explanation = explanation.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+        bot.send_message(message.chat.id, format!("<pre>{}</pre>", explanation))
+            .parse_mode(ParseMode::Html)
+            .await?;
+        Ok(())
+    }
+
     pub async fn format_guide(bot: AutoSend<Bot>, message: Message) -> HandlerResult {
         bot.send_message(
             message.chat.id,
@@ -120,6 +167,57 @@ pub mod executors {
                     message.chat.id, error_messages::INVALID_CONDITION_MESSAGE
                 ).await?;
             }
+            ValidErrorKind::InvalidAlphabet => {
+                bot.send_message(
+                    message.chat.id, error_messages::INVALID_ALPHABET_MESSAGE
+                ).await?;
+            }
+            ValidErrorKind::NumberTooLong => {
+                bot.send_message(
+                    message.chat.id, error_messages::NUMBER_TOO_LONG_MESSAGE
+                ).await?;
+            }
+            ValidErrorKind::ExplanationTooLong => {
+                bot.send_message(
+                    message.chat.id, error_messages::EXPLANATION_TOO_LONG_MESSAGE
+                ).await?;
+            }
+            ValidErrorKind::UnrepresentableNumber => {
+                bot.send_message(
+                    message.chat.id, error_messages::UNREPRESENTABLE_NUMBER_MESSAGE
+                ).await?;
+            }
+            ValidErrorKind::Overflow => {
+                bot.send_message(
+                    message.chat.id, error_messages::OVERFLOW_MESSAGE
+                ).await?;
+            }
+            ValidErrorKind::InvalidBcdNibble(nibble, position) => {
+                bot.send_message(
+                    message.chat.id,
+                    format!("{} {:04b} (digit {})", error_messages::INVALID_BCD_NIBBLE_MESSAGE, nibble, position)
+                ).await?;
+            }
+            ValidErrorKind::InvalidExpression => {
+                bot.send_message(
+                    message.chat.id, error_messages::INVALID_EXPRESSION_MESSAGE
+                ).await?;
+            }
+            ValidErrorKind::DivisionByZero => {
+                bot.send_message(
+                    message.chat.id, error_messages::DIVISION_BY_ZERO_MESSAGE
+                ).await?;
+            }
+            ValidErrorKind::MissingWidth => {
+                bot.send_message(
+                    message.chat.id, error_messages::MISSING_WIDTH_MESSAGE
+                ).await?;
+            }
+            ValidErrorKind::InvalidZeckendorfNumber => {
+                bot.send_message(
+                    message.chat.id, error_messages::INVALID_ZECKENDORF_NUMBER_MESSAGE
+                ).await?;
+            }
             ValidErrorKind::UnknownError => {
                 bot.send_message(MAINTAINER_ID, text).await?;
                 bot.send_message(
@@ -146,6 +244,47 @@ const FORMAT_GUIDE_MESSAGE: &str =
     or\n\
     fract_numsys > mantissalen_desirednumsys\n\
     \n\
+    Repeating digits go in parentheses: 0.1(6)_numsys\n\
+    \n\
+    Negative numbers start with a minus: -integer_numsys\n\
+    \n\
     \n\
-    Number system must be between 2 and 36 inclusively\n\
+    Number system must be between 2 and 62 inclusively, or 64\n\
+    Above 36 digits are case-sensitive: 0-9A-Za-z, base 64 uses A-Za-z0-9+/\n\
+    58btc is base 58 with the Bitcoin alphabet\n\
+    Your own digits go in braces: GATTACA_{ACGT} > 10\n\
+    Balanced systems have an odd base: 1T0_bal3 > 10, 42_10 > bal5\n\
+    Negative balanced digits are T in bal3 and overlined elsewhere: 2̅\n\
+    Negative bases go from -2 to -36: 1101_-2 > 10\n\
+    Complex bases are 2i and -1+i: 3+4i_10 > 2i\n\
+    Factorial base is !: 463_10 > !\n\
+    Permutations of n elements by rank: 463_10 > perm6, 4,6,2,1,5,3_perm > 10\n\
+    Zeckendorf is fib and Fibonacci coding is fibcode: 100_10 > fib\n\
+    Roman numerals go up to 3999999: MCMXCIV > 16, 1994_10 > roman\n\
+    Use romanlax to accept forms like IIII, and overlines or CIↃ above 3999\n\
+    Spreadsheet columns are col: XFD_col > 10, 16384_10 > col\n\
+    Other bijective bases go from 2 to 35 with digits 1-9A-Z: 20_10 > bij10\n\
+    Mixed radices go in brackets: 100000_10 > [24,60,60], 1:03:46:40_[24,60,60] > 10\n\
+    Named mixed radices are hms, dhms and dms for degrees\n\
+    Exponents are decimal powers of the base, after e, p or ^: 6.02e23_10 > 16, 1.01p+5_2 > 10\n\
+    Add sci to write the result in scientific notation: 1000_10 > sci2\n\
+    Numbers can be sent as 0x, 0o or 0b literals: 0xFF > 10\n\
+    IEEE 754 floats are f16, f32, f64 and f128: 3.14159_10 > f32\n\
+    ML floats are bf16, tf32 and FP8 e4m3 and e5m2, which saturate: 500_10 > e4m3\n\
+    A literal is decoded as a float's bits: 0x40490FDB > f32, or 40490FDB_f32 > 10\n\
+    Integer width goes last, i for signed and u for unsigned: -5_10 > 2 i8, 255_10 > 16 u8\n\
+    A literal is then read as the integer's bits: 0xFB > 10 i8\n\
+    Fixed-point Q formats count the sign in the integer bits: 3.14159_10 > Q4.12, 0x3244 > Q4.12\n\
+    UQ formats are unsigned: 0.5_10 > UQ0.8\n\
+    Binary-coded decimals are bcd, ubcd for unpacked, xs3 and aiken: 1994_10 > bcd, 0x4CCC > xs3\n\
+    Gray code is gray, and gray3 to gray36 in other bases: 13_10 > gray, 1011_gray > 10\n\
+    A cut mantissa is truncated unless a rounding mode goes last: 0.(6)_10 > 3_10 halfeven\n\
+    Rounding modes are halfeven, halfup, truncate, ceiling and floor, /rounding sets your own\n\
+    Expressions take + - * /, // for integer division, % and ^, with or without spaces:\n\
+    (FF_16 + 1011_2) * 7_8 > 10, 2_10^-3_10 > 2\n\
+    Bitwise operators are & | xor and ~, shifts << >>, rotations rol ror and rev for bit reversal:\n\
+    0xF0 & 0x3C > 2\n\
+    An integer width goes after the expression or the condition: ~0x0F u8 > 2, 0x81 rol 1_10 > 16 u8\n\
+    /explain shows the steps of a conversion between positional bases: /explain 13.625_10 > 2\n\
+    Number must not be longer than 4096 digits\n\
     Mantissa length must be between 0 and 50 inclusively";
\ No newline at end of file
@@ -1,5 +1,37 @@
+use std::cmp::Ordering;
+use std::io::ErrorKind;
+use num_rational::BigRational;
+use num_bigint::{BigInt, BigUint};
+use num_traits::{Signed, Zero};
+
+use crate::digits::alphabets;
+use crate::digits::alphabets::Alphabet;
+use crate::digits::balanced;
+use crate::digits::bcd::DecimalCode;
+use crate::digits::bijective;
+use crate::digits::complex;
+use crate::digits::complex::{ComplexBase, ComplexRational};
 use crate::digits::converting;
-use crate::digits::converting::DIGITMASK;
+use crate::digits::converting::{Expansion, MixedRadix, RoundingMode, Steps};
+use crate::digits::factoradic;
+use crate::digits::gray;
+use crate::digits::fixed_point::QFormat;
+use crate::digits::fixed_width::IntegerWidth;
+use crate::digits::ieee754;
+use crate::digits::ieee754::{FloatFormat, FloatValue};
+use crate::digits::negabase;
+use crate::digits::roman;
+use crate::digits::zeckendorf;
+use crate::expression;
+
+/// The longest initial number the bot agrees to convert. It is enough for a 4096-bit number written in binary.
+pub const MAX_NUMBER_LENGTH: usize = 4096;
+
+/// The most digits after the point the bot writes, including the repeating block of an exact expansion.
+pub const MAX_MANTISSA_LENGTH: i32 = 50;
+
+/// The longest explanation the bot sends. It is the most characters a Telegram message can have.
+pub const MAX_EXPLANATION_LENGTH: usize = 4096;
 
 /// Class that performs different base conversions of numbers.
 pub struct Converter {
@@ -8,11 +40,31 @@ pub struct Converter {
 }
 
 impl Converter {
+    #[cfg(test)]
     pub fn convert_from_message(text: &str) -> Self {
+        Self::convert_with_rounding(text, RoundingMode::default())
+    }
+
+    /// Converts a query, cutting the mantissa with `rounding` unless the query names its own rounding mode.
+    pub fn convert_with_rounding(text: &str, rounding: RoundingMode) -> Self {
+        Self::convert(text, rounding, None)
+    }
+
+    /// Converts a query. A conversion between positional systems records its steps in `explanation`.
+    fn convert(text: &str, mut rounding: RoundingMode, explanation: Option<&mut Option<Steps>>) -> Self {
+        // Several numbers with operators go before the last `>`: (FF_16 + 1011_2) * 7_8 > 10. //
+        // Operators need no spaces around them, and a bitwise not sticks to its number: ~5_10 > 2. //
+        // A number whose digits look like operators is still one number: D/_64 > 10. //
+        if let Some((expression, condition)) = text.rsplit_once(" > ") {
+            if expression.contains(' ') || (!is_single_number(expression, condition) && expression::is_expression(expression)) {
+                return Self::convert_expression(expression, condition, rounding);
+            }
+        }
+
         let all_elems: Vec<&str> = text.split(' ').collect::<Vec<&str>>();
 
         // Checks if the input is formatted correctly. //
-        if all_elems.len() > 3 {
+        if all_elems.len() > 4 {
             return Self { result: None, error_kind: Some(ValidErrorKind::InvalidFormat) }
         }
         if all_elems.len() < 3 {
@@ -22,10 +74,34 @@ impl Converter {
             return Self { result: None, error_kind: Some(ValidErrorKind::InvalidFormat) }
         }
 
-        let initial: Vec<&str> = all_elems[0].split('_').collect::<Vec<&str>>();
+        let mut initial: Vec<&str> = all_elems[0].split('_').collect::<Vec<&str>>();
         let condition: Vec<&str> = all_elems[2].split('_').collect::<Vec<&str>>();
         let result: String;
 
+        // A rounding mode after the condition applies to this query only: 1.(3)_10 > 2_10 halfup. //
+        // An integer width there asks for a fixed-width integer: -5_10 > 2 i8. //
+        if all_elems.len() == 4 {
+            if let Some(mode) = RoundingMode::from_name(all_elems[3]) {
+                rounding = mode;
+            } else {
+                return Self::convert_fixed_width(&initial, &condition, all_elems[3]);
+            }
+        }
+
+        // Roman numerals and 0x or 0b literals can be sent on their own: MCMXCIV > 10. //
+        // A literal is a bit pattern when the condition is a binary format: 0x40490FDB > f32. //
+        if initial.len() == 1 {
+            let condition_suffix = condition[condition.len() - 1];
+            if let Some((digits, base)) = split_literal_prefix(initial[0]) {
+                if NumberSystem::from_suffix(condition_suffix).is_some_and(|system| system.is_binary_format()) {
+                    initial.push(condition_suffix);
+                } else {
+                    initial = vec![digits, base];
+                }
+            } else if roman::is_numeral(initial[0]) {
+                initial.push("roman");
+            }
+        }
         if initial.len() < 2 || initial.len() > 2 {
             return Self { result: None, error_kind: Some(ValidErrorKind::InvalidInitialNumber) }
         }
@@ -35,55 +111,104 @@ impl Converter {
         // End of checking. //
 
         let initial_number: String = initial[0].to_owned();
-        let initial_base: i32;
         let mut dot_count = 0u8;
 
-        if let Ok(base) = initial[1].parse::<i32>() {
-            initial_base = base;
-        } else {
-            return Self { result: None, error_kind: Some(ValidErrorKind::InvalidInitialBase) }
+        if initial_number.chars().count() > MAX_NUMBER_LENGTH {
+            return Self { result: None, error_kind: Some(ValidErrorKind::NumberTooLong) }
+        }
+
+        let initial_system = NumberSystem::from_suffix(initial[1]);
+        let mut condition_system = NumberSystem::from_suffix(condition[condition.len() - 1]);
+
+        // A number sent in scientific notation is written back in it. //
+        if let (Some(NumberSystem::Positional(base, alphabet)), Some(NumberSystem::Positional(base_to, alphabet_to))) =
+            (&initial_system, &condition_system)
+        {
+            if converting::split_exponent(&initial_number, *base, alphabet).is_some() {
+                condition_system = Some(NumberSystem::Scientific(*base_to, alphabet_to.clone()));
+            }
         }
 
-        if !(2..=36).contains(&initial_base) {
-            return Self { result: None, error_kind: Some(ValidErrorKind::InvalidInitialBase) }
+        let (initial_base, initial_alphabet) = match (initial_system, condition_system) {
+            (None, _) => {
+                return Self { result: None, error_kind: Some(suffix_error(initial[1], ValidErrorKind::InvalidInitialBase)) }
+            }
+            (Some(NumberSystem::Positional(base, alphabet)), None | Some(NumberSystem::Positional(..))) => {
+                (base, alphabet)
+            }
+            (Some(initial_system), condition_system) => {
+                return Self::convert_exact(&initial_number, initial_system, condition_system, &condition, rounding)
+            }
+        };
+
+        // The sign is the only non-digit allowed before the number. //
+        let unsigned_number = initial_number.strip_prefix('-').unwrap_or(&initial_number);
+        if unsigned_number.is_empty() || unsigned_number == "." {
+            return Self { result: None, error_kind: Some(ValidErrorKind::InvalidInitialNumber) }
         }
 
-        for char in initial_number.to_uppercase().chars() {
-            if let Some(index) = DIGITMASK.find(char) {
+        let mut period_opened = false;
+        let mut period_closed = false;
+        let mut period_length = 0;
+
+        for char in unsigned_number.chars() {
+            if period_closed {
+                return Self { result: None, error_kind: Some(ValidErrorKind::InvalidInitialNumber) }
+            }
+
+            if let Some(index) = initial_alphabet.value_of(char) {
                 if index as i32 >= initial_base {
                     return Self { result: None, error_kind: Some(ValidErrorKind::InvalidInitialNumber) }
                 }
+                if period_opened { period_length += 1 }
             } else if char == '.' {
                 dot_count += 1;
                 if dot_count > 1 {
                     return Self { result: None, error_kind: Some(ValidErrorKind::InvalidInitialNumber) }
                 }
-            } else{
+            } else if char == '(' && dot_count == 1 && !period_opened {
+                period_opened = true;
+            } else if char == ')' && period_opened {
+                // A repeating block must have digits: 0.()_10 is refused. //
+                if period_length == 0 {
+                    return Self { result: None, error_kind: Some(ValidErrorKind::InvalidInitialNumber) }
+                }
+                period_closed = true;
+            } else {
                 return Self { result: None, error_kind: Some(ValidErrorKind::InvalidInitialNumber) }
             }
         }
+        if period_opened && !period_closed {
+            return Self { result: None, error_kind: Some(ValidErrorKind::InvalidInitialNumber) }
+        }
 
         let condition_base: i32;
+        let condition_alphabet: Alphabet;
         let mut mantissa_length: Option<i32> = None;
 
         if dot_count == 0 {
-            if let Ok(base) = condition[0].parse::<i32>() {
+            if let Some((base, alphabet)) = alphabets::from_suffix(condition[0]) {
                 condition_base = base;
+                condition_alphabet = alphabet;
+            } else if condition[0].parse::<i32>().is_ok() {
+                return Self { result: None, error_kind: Some(suffix_error(condition[0], ValidErrorKind::InvalidConditionBase)) }
             } else {
-                return Self { result: None, error_kind: Some(ValidErrorKind::InvalidCondition) }
+                return Self { result: None, error_kind: Some(suffix_error(condition[0], ValidErrorKind::InvalidCondition)) }
             }
         } else if dot_count == 1 {
             if condition.len() == 1 {
-                if let Ok(base) = condition[0].parse::<i32>() {
+                if let Some((base, alphabet)) = alphabets::from_suffix(condition[0]) {
                     condition_base = base;
+                    condition_alphabet = alphabet;
                 } else {
-                    return Self { result: None, error_kind: Some(ValidErrorKind::InvalidConditionBase) }
+                    return Self { result: None, error_kind: Some(suffix_error(condition[0], ValidErrorKind::InvalidConditionBase)) }
                 }
             } else if condition.len() == 2 {
-                if let Ok(base) = condition[1].parse::<i32>() {
+                if let Some((base, alphabet)) = alphabets::from_suffix(condition[1]) {
                     condition_base = base;
+                    condition_alphabet = alphabet;
                 } else {
-                    return Self { result: None, error_kind: Some(ValidErrorKind::InvalidConditionBase) }
+                    return Self { result: None, error_kind: Some(suffix_error(condition[1], ValidErrorKind::InvalidConditionBase)) }
                 }
 
                 if let Ok(len) = condition[0].parse::<i32>() {
@@ -98,38 +223,688 @@ impl Converter {
             return Self { result: None, error_kind: Some(ValidErrorKind::InvalidCondition) }
         }
 
-        if !(2..=36).contains(&condition_base) {
-            return Self { result: None, error_kind: Some(ValidErrorKind::InvalidConditionBase) }
-        }
         if let Some(len) = mantissa_length {
-            if len > 50 {
+            if !(0..=MAX_MANTISSA_LENGTH).contains(&len) {
                 return Self { result: None, error_kind: Some(ValidErrorKind::InvalidMantissaLength) }
             }
         }
 
+        let mut steps = explanation.map(|steps| steps.insert(Steps::new(initial_base, condition_base, &condition_alphabet)));
+
         if dot_count == 0 {
             result =
-                if let Ok(res) = converting::any_whole_to_any(&initial_number.to_uppercase(),
-                                                              initial_base, condition_base)
+                if let Ok(res) = converting::any_whole_to_any(&initial_number,
+                                                              initial_base, condition_base,
+                                                              &initial_alphabet, &condition_alphabet,
+                                                              steps.as_deref_mut())
                 { res }
                 else {
                     return Self { result: None, error_kind: Some(ValidErrorKind::UnknownError) }
                 }
         } else if dot_count == 1 {
-            result =
-                if let Ok(res) = converting::any_fractional_to_any(&initial_number.to_uppercase(),
-                                                                   initial_base, condition_base,
-                                                                   mantissa_length)
-                { res }
-                else {
-                    return Self { result: None, error_kind: Some(ValidErrorKind::UnknownError) }
-                }
+            // Without an explicit mantissa length the exact expansion is shown if it is short enough. //
+            let expansion =
+                if mantissa_length.is_none() {
+                    converting::any_fractional_to_any_periodic(&initial_number,
+                                                               initial_base, condition_base,
+                                                               MAX_MANTISSA_LENGTH as usize,
+                                                               &initial_alphabet, &condition_alphabet,
+                                                               steps.as_deref_mut())
+                } else {
+                    Ok(None)
+                };
+
+            result = match expansion {
+                Ok(Some(expansion)) => describe_expansion(expansion),
+                Ok(None) =>
+                    if let Ok((res, ordering)) = converting::any_fractional_to_any_rounded(&initial_number,
+                                                                                           initial_base, condition_base,
+                                                                                           mantissa_length, rounding,
+                                                                                           &initial_alphabet, &condition_alphabet,
+                                                                                           steps)
+                    { describe_rounding(res, Some(ordering)) }
+                    else {
+                        return Self { result: None, error_kind: Some(ValidErrorKind::UnknownError) }
+                    },
+                Err(_) => return Self { result: None, error_kind: Some(ValidErrorKind::UnknownError) },
+            }
         } else {
             return Self { result: None, error_kind: Some(ValidErrorKind::UnknownError) }
         }
 
         Self { result: Some(result), error_kind: None }
     }
+
+    /// Converts a number between positional systems and explains every step of it:
+    /// the positional expansion, the repeated division and the repeated multiplication.
+    /// The steps are recorded by the conversion itself, so they end with the reply `convert_with_rounding` gives.
+    pub fn explain_from_message(text: &str, rounding: RoundingMode) -> Self {
+        let mut steps = None;
+        let converter = Self::convert(text, rounding, Some(&mut steps));
+        let (result, steps) = match (converter.result, steps) {
+            (Some(result), Some(steps)) => (result, steps),
+            (Some(_), None) => return Self { result: None, error_kind: Some(ValidErrorKind::InvalidCondition) },
+            (None, _) => return Self { result: None, error_kind: converter.error_kind },
+        };
+
+        // Long tables are refused before they are written, as writing them takes a while. //
+        if steps.min_length() > MAX_EXPLANATION_LENGTH {
+            return Self { result: None, error_kind: Some(ValidErrorKind::ExplanationTooLong) }
+        }
+        let explanation = steps.describe(&result);
+        if explanation.chars().count() > MAX_EXPLANATION_LENGTH {
+            return Self { result: None, error_kind: Some(ValidErrorKind::ExplanationTooLong) }
+        }
+
+        Self { result: Some(explanation), error_kind: None }
+    }
+
+    /// Stores a number in a fixed-width integer and writes the bit pattern in the condition base,
+    /// or reads a `0x`, `0o` or `0b` bit pattern back into a number. Signed integers are shown
+    /// in two's complement, one's complement and sign-magnitude forms.
+    fn convert_fixed_width(initial: &[&str], condition: &[&str], width: &str) -> Self {
+        let width = if let Some(width) = IntegerWidth::from_suffix(width) {
+            width
+        } else {
+            return Self { result: None, error_kind: Some(ValidErrorKind::InvalidCondition) }
+        };
+        if condition.len() != 1 {
+            return Self { result: None, error_kind: Some(ValidErrorKind::InvalidCondition) }
+        }
+        let (base, alphabet) = if let Some(system) = alphabets::from_suffix(condition[0]) {
+            system
+        } else {
+            return Self { result: None, error_kind: Some(suffix_error(condition[0], ValidErrorKind::InvalidConditionBase)) }
+        };
+        if initial[0].chars().count() > MAX_NUMBER_LENGTH {
+            return Self { result: None, error_kind: Some(ValidErrorKind::NumberTooLong) }
+        }
+
+        // A bit pattern is read in every form the integer could be stored in. //
+        if let (1, Some((digits, pattern_base))) = (initial.len(), split_literal_prefix(initial[0])) {
+            let pattern = match converting::parse_whole(digits, pattern_base.parse().unwrap(), &alphabets::STANDARD) {
+                Ok(pattern) if !pattern.is_negative() => pattern.magnitude().clone(),
+                _ => return Self { result: None, error_kind: Some(ValidErrorKind::InvalidInitialNumber) },
+            };
+            if !width.holds(&pattern) {
+                return Self { result: None, error_kind: Some(ValidErrorKind::Overflow) }
+            }
+
+            let write = |value: BigInt| converting::format_whole(value, base, &alphabet);
+            let result = if width.signed {
+                describe_signed(
+                    write(width.read_twos_complement(&pattern)),
+                    write(width.read_ones_complement(&pattern)),
+                    write(width.read_sign_magnitude(&pattern)),
+                )
+            } else {
+                write(width.read_twos_complement(&pattern))
+            };
+            return Self { result: Some(result), error_kind: None }
+        }
+
+        if initial.len() != 2 {
+            return Self { result: None, error_kind: Some(ValidErrorKind::InvalidInitialNumber) }
+        }
+        let number = match NumberSystem::from_suffix(initial[1]).map(|system| system.parse(initial[0], false)) {
+            Some(Ok(number)) => number,
+            Some(Err(error_kind)) => return Self { result: None, error_kind: Some(error_kind) },
+            None => return Self { result: None, error_kind: Some(suffix_error(initial[1], ValidErrorKind::InvalidInitialBase)) },
+        };
+        let value = match whole(&number.re) {
+            Ok(value) if number.im.is_zero() => value,
+            _ => return Self { result: None, error_kind: Some(ValidErrorKind::UnrepresentableNumber) },
+        };
+
+        let write = |pattern: Option<BigUint>| match pattern {
+            Some(pattern) => write_pattern(pattern, width.bits, base, &alphabet),
+            None => "overflow".to_owned(),
+        };
+        let twos_complement = width.twos_complement(&value);
+        if twos_complement.is_none() {
+            return Self { result: None, error_kind: Some(ValidErrorKind::Overflow) }
+        }
+
+        let result = if width.signed {
+            describe_signed(write(twos_complement), write(width.ones_complement(&value)), write(width.sign_magnitude(&value)))
+        } else {
+            write(twos_complement)
+        };
+        Self { result: Some(result), error_kind: None }
+    }
+
+    /// Converts a number when either side of the query isn't a plain positional system.
+    /// The number goes through an exact value, since balanced, negative-base and complex-base
+    /// numbers carry their sign in the digits.
+    fn convert_exact(
+        initial_number: &str,
+        initial_system: NumberSystem,
+        condition_system: Option<NumberSystem>,
+        condition: &[&str],
+        rounding: RoundingMode,
+    ) -> Self {
+        let (condition_system, mantissa_length) = match read_condition(condition, condition_system) {
+            Ok(condition) => condition,
+            Err(error_kind) => return Self { result: None, error_kind: Some(error_kind) },
+        };
+
+        // A bit pattern decoded in its own format is described as it is, so infinities and NaNs are kept. //
+        let description = match (&initial_system, &condition_system) {
+            (NumberSystem::Float(format), NumberSystem::Float(format_to)) if format == format_to => {
+                Some(format.parse_bits(initial_number).map(|bits| format.describe(&bits, None)))
+            }
+            (NumberSystem::FixedPoint(format), NumberSystem::FixedPoint(format_to)) if format == format_to => {
+                Some(format.parse_raw(initial_number).map(|raw| format.describe(&raw, None, false)))
+            }
+            (NumberSystem::Decimal(code), NumberSystem::Decimal(code_to)) if code == code_to => {
+                Some(code.parse_nibbles(initial_number)
+                    .and_then(|nibbles| code.decode(&nibbles).map(|_| code.describe(&nibbles))))
+            }
+            _ => None,
+        };
+        match description {
+            Some(Ok(description)) => return Self { result: Some(description), error_kind: None },
+            Some(Err(_)) => return Self { result: None, error_kind: Some(initial_system.invalid_number_error(initial_number)) },
+            None => {}
+        }
+
+        // A positional number is only read as `a+bi` when it is converted to a complex base. //
+        let complex_literal = matches!(condition_system, NumberSystem::Complex(_));
+        let number = match initial_system.parse(initial_number, complex_literal) {
+            Ok(number) => number,
+            Err(error_kind) => return Self { result: None, error_kind: Some(error_kind) },
+        };
+
+        match condition_system.format(number, mantissa_length, rounding) {
+            Ok(result) => Self { result: Some(result), error_kind: None },
+            Err(error_kind) => Self { result: None, error_kind: Some(error_kind) },
+        }
+    }
+
+    /// Evaluates an arithmetic expression over numbers in any number systems
+    /// and writes the result in the condition system. Bitwise expressions also get
+    /// the bits of their numbers and of the result lined up under each other.
+    fn convert_expression(expression: &str, condition: &str, rounding: RoundingMode) -> Self {
+        if expression.chars().count() > MAX_NUMBER_LENGTH {
+            return Self { result: None, error_kind: Some(ValidErrorKind::NumberTooLong) }
+        }
+
+        // An integer width may close the expression: ~0x0F u8 > 2. //
+        let mut expression = expression;
+        let mut width: Option<IntegerWidth> = None;
+        if let Some((rest, suffix)) = expression.rsplit_once(' ') {
+            if let Some(integer_width) = IntegerWidth::from_suffix(suffix) {
+                expression = rest;
+                width = Some(integer_width);
+            }
+        }
+
+        // A rounding mode or a width may follow the condition, as after a single number. //
+        let (condition, rounding) = match condition.split(' ').collect::<Vec<&str>>()[..] {
+            [condition] => (condition, rounding),
+            [condition, last] => match (RoundingMode::from_name(last), IntegerWidth::from_suffix(last)) {
+                (Some(mode), _) => (condition, mode),
+                (None, Some(integer_width)) if width.is_none() => {
+                    width = Some(integer_width);
+                    (condition, rounding)
+                }
+                _ => return Self { result: None, error_kind: Some(ValidErrorKind::InvalidCondition) },
+            },
+            _ => return Self { result: None, error_kind: Some(ValidErrorKind::InvalidFormat) },
+        };
+
+        let condition: Vec<&str> = condition.split('_').collect::<Vec<&str>>();
+        if condition.len() > 2 {
+            return Self { result: None, error_kind: Some(ValidErrorKind::InvalidCondition) }
+        }
+        let condition_system = NumberSystem::from_suffix(condition[condition.len() - 1]);
+        let (condition_system, mantissa_length) = match read_condition(&condition, condition_system) {
+            Ok(condition) => condition,
+            Err(error_kind) => return Self { result: None, error_kind: Some(error_kind) },
+        };
+
+        let complex_literal = matches!(condition_system, NumberSystem::Complex(_));
+        let read = |operand: &str| read_operand(operand, complex_literal, width);
+        let tree = match expression::parse(expression) {
+            Ok(tree) => tree,
+            Err(error_kind) => return Self { result: None, error_kind: Some(error_kind) },
+        };
+        let number = match tree.evaluate(&read, width) {
+            Ok(number) => number,
+            Err(error_kind) => return Self { result: None, error_kind: Some(error_kind) },
+        };
+
+        let bits = if tree.is_bitwise() {
+            let operands = tree.bit_operands().into_iter()
+                .map(|(negated, operand)| {
+                    let value = expression::whole(&read(operand)?)?;
+                    let value = if negated { -value } else { value };
+                    let value = width.map_or(value.clone(), |width| width.wrap(&value));
+                    Ok((format!("{}{}", if negated { "-" } else { "" }, operand), value))
+                })
+                .collect::<Result<Vec<_>, ValidErrorKind>>();
+            match (operands, expression::whole(&number)) {
+                (Ok(operands), Ok(result)) => describe_bits(&operands, &result, width),
+                _ => None,
+            }
+        } else {
+            None
+        };
+
+        match (condition_system.format(number, mantissa_length, rounding), bits) {
+            (Ok(result), Some(bits)) => Self { result: Some(format!("{}\n\n{}", result, bits)), error_kind: None },
+            (Ok(result), None) => Self { result: Some(result), error_kind: None },
+            (Err(error_kind), _) => Self { result: None, error_kind: Some(error_kind) },
+        }
+    }
+}
+
+/// Lists the forms a signed integer can be stored in, one per line.
+fn describe_signed(twos_complement: String, ones_complement: String, sign_magnitude: String) -> String {
+    format!("Two's complement: {}\nOne's complement: {}\nSign-magnitude: {}", twos_complement, ones_complement, sign_magnitude)
+}
+
+/// Writes the bits of the numbers of a bitwise expression above the bits of its result,
+/// in nibbles lined up to the right. Without a width the numbers get just enough bits,
+/// and one more for the sign if some of them are negative.
+fn describe_bits(operands: &[(String, BigInt)], result: &BigInt, width: Option<IntegerWidth>) -> Option<String> {
+    let values = || operands.iter().map(|(_, value)| value).chain([result]);
+    let width = match width {
+        Some(width) => width,
+        None => {
+            let signed = values().any(|value| value.is_negative());
+            let magnitude_bits = |value: &BigInt| if value.is_negative() { (-value - 1u32).bits() } else { value.bits() };
+            let bits = values().map(magnitude_bits).max().unwrap_or(0).max(1) + signed as u64;
+            IntegerWidth::new(u32::try_from(bits).ok()?, signed)?
+        }
+    };
+
+    let write = |value: &BigInt| {
+        let pattern = write_pattern(width.twos_complement(value).unwrap(), width.bits, 2, &alphabets::STANDARD);
+        let digits: Vec<char> = pattern.chars().collect();
+        let nibbles: Vec<String> = digits.rchunks(4).rev().map(|nibble| nibble.iter().collect()).collect();
+        nibbles.join(" ")
+    };
+
+    let mut lines: Vec<String> = operands.iter().map(|(operand, value)| format!("  {}  {}", write(value), operand)).collect();
+    lines.push(format!("= {}", write(result)));
+    Some(lines.join("\n"))
+}
+
+/// Checks the condition system and reads the mantissa length written before it.
+fn read_condition(condition: &[&str], condition_system: Option<NumberSystem>) -> Result<(NumberSystem, Option<i32>), ValidErrorKind> {
+    let condition_system = if let Some(system) = condition_system {
+        system
+    } else {
+        let suffix = condition[condition.len() - 1];
+        return Err(suffix_error(suffix, ValidErrorKind::InvalidConditionBase));
+    };
+
+    let mut mantissa_length: Option<i32> = None;
+    if condition.len() == 2 {
+        match condition[0].parse::<i32>() {
+            Ok(len) if (0..=MAX_MANTISSA_LENGTH).contains(&len) => mantissa_length = Some(len),
+            _ => return Err(ValidErrorKind::InvalidMantissaLength),
+        }
+    }
+
+    Ok((condition_system, mantissa_length))
+}
+
+/// Tells whether the left side of a query reads as one number of its system,
+/// so that digits like `+`, `/` or `-` in `A+B_64`, `D/_64` and `1-1_bal3` aren't taken for operators.
+fn is_single_number(initial: &str, condition: &str) -> bool {
+    let suffix = condition.split(' ').next().and_then(|condition| condition.rsplit('_').next()).unwrap_or_default();
+    let complex_literal = matches!(NumberSystem::from_suffix(suffix), Some(NumberSystem::Complex(_)));
+
+    read_operand(initial, complex_literal, None).is_ok()
+}
+
+/// Reads a number of an expression. It is written like the initial number of a query,
+/// so 0x, 0o and 0b literals and Roman numerals may go without a number system.
+/// With an integer width such a literal is the integer's bits, so 0xFF is -1 in `i8`.
+fn read_operand(operand: &str, complex_literal: bool, width: Option<IntegerWidth>) -> Result<ComplexRational, ValidErrorKind> {
+    let mut parts: Vec<&str> = operand.split('_').collect::<Vec<&str>>();
+    if parts.len() == 1 {
+        if let Some((digits, base)) = split_literal_prefix(parts[0]) {
+            if let Some(width) = width {
+                let pattern = match converting::parse_whole(digits, base.parse().unwrap(), &alphabets::STANDARD) {
+                    Ok(pattern) if !pattern.is_negative() => pattern.magnitude().clone(),
+                    _ => return Err(ValidErrorKind::InvalidInitialNumber),
+                };
+                if !width.holds(&pattern) { return Err(ValidErrorKind::Overflow) }
+
+                return Ok(ComplexRational::from(BigRational::from(width.read_twos_complement(&pattern))));
+            }
+            parts = vec![digits, base];
+        } else if roman::is_numeral(parts[0]) {
+            parts.push("roman");
+        }
+    } else if let Some((digits, base)) = split_literal_prefix(parts[0]) {
+        // The prefix may repeat the number system: 0x0F_16. //
+        if parts.len() == 2 && parts[1] == base {
+            parts[0] = digits;
+        }
+    }
+    if parts.len() != 2 {
+        return Err(ValidErrorKind::InvalidInitialNumber);
+    }
+
+    match NumberSystem::from_suffix(parts[1]) {
+        Some(system) => system.parse(parts[0], complex_literal),
+        None => Err(suffix_error(parts[1], ValidErrorKind::InvalidInitialBase)),
+    }
+}
+
+/// A number system written after `_` in a query.
+enum NumberSystem {
+    /// Digits from 0 to `base - 1`, written with the alphabet.
+    Positional(i32, Alphabet),
+    /// Digits from -(base - 1)/2 to (base - 1)/2, written with a `bal` prefix: `bal3`.
+    Balanced(i32),
+    /// Digits from 0 to |base| - 1 with a base from -2 to -36.
+    Negative(i32),
+    /// Base `2i` or `-1+i`.
+    Complex(ComplexBase),
+    /// The factorial number system, written as `!`.
+    Factoradic,
+    /// A permutation of 1..=n written as `4,6,2,1,5,3`, standing for its lexicographic rank.
+    /// As a condition, `perm6` fixes the number of elements.
+    Permutation(Option<usize>),
+    /// Zeckendorf representation `fib`, or Fibonacci coding `fibcode` if the flag is set.
+    Zeckendorf(bool),
+    /// Roman numerals, `roman` with strict parsing or `romanlax` with lenient parsing.
+    Roman(bool),
+    /// Bijective numeration with no zero digit, like `bij10`, or spreadsheet columns `col`.
+    /// The flag tells that digits are letters.
+    Bijective(i32, bool),
+    /// A radix for every position, like `[24,60,60]`, or a named one like `hms`.
+    MixedRadix(MixedRadix),
+    /// A positional system written in scientific notation, with a `sci` prefix: `sci16`.
+    Scientific(i32, Alphabet),
+    /// A binary float format like `f32` or `bf16`. Numbers in it are bit patterns written in hexadecimal.
+    Float(FloatFormat),
+    /// A fixed-point format like `Q4.12`. Numbers in it are bit patterns written in hexadecimal.
+    FixedPoint(QFormat),
+    /// A binary-coded decimal like `bcd` or `xs3`. Numbers in it are bit patterns written in hexadecimal.
+    Decimal(DecimalCode),
+    /// A reflected Gray code, binary `gray` or in another base like `gray3`.
+    Gray(i32),
+}
+
+impl NumberSystem {
+    fn from_suffix(suffix: &str) -> Option<Self> {
+        if let Some(base) = ComplexBase::from_suffix(suffix) {
+            return Some(Self::Complex(base));
+        }
+
+        if suffix == "!" {
+            return Some(Self::Factoradic);
+        }
+
+        if let Some(format) = ieee754::from_suffix(suffix) {
+            return Some(Self::Float(format));
+        }
+
+        if let Some(format) = QFormat::from_suffix(suffix) {
+            return Some(Self::FixedPoint(format));
+        }
+
+        if let Some(code) = DecimalCode::from_suffix(suffix) {
+            return Some(Self::Decimal(code));
+        }
+
+        if let Some(suffix) = suffix.strip_prefix("sci") {
+            return alphabets::from_suffix(suffix).map(|(base, alphabet)| Self::Scientific(base, alphabet));
+        }
+
+        if let Some(system) = MixedRadix::from_suffix(suffix) {
+            return Some(Self::MixedRadix(system));
+        }
+
+        if let Some(base) = suffix.strip_prefix("gray") {
+            let base = if base.is_empty() { 2 } else { base.parse::<i32>().ok()? };
+            return gray::is_valid_base(base).then_some(Self::Gray(base));
+        }
+
+        if suffix == "col" {
+            return Some(Self::Bijective(bijective::COLUMN_BASE, true));
+        }
+
+        if let Some(base) = suffix.strip_prefix("bij") {
+            let base = base.parse::<i32>().ok()?;
+            return bijective::is_valid_base(base).then_some(Self::Bijective(base, false));
+        }
+
+        if suffix == "roman" || suffix == "romanlax" {
+            return Some(Self::Roman(suffix == "roman"));
+        }
+
+        if suffix == "fib" || suffix == "fibcode" {
+            return Some(Self::Zeckendorf(suffix == "fibcode"));
+        }
+
+        if let Some(elements) = suffix.strip_prefix("perm") {
+            if elements.is_empty() {
+                return Some(Self::Permutation(None));
+            }
+            let elements = elements.parse::<usize>().ok()?;
+            return (elements > 0).then_some(Self::Permutation(Some(elements)));
+        }
+
+        if let Some(base) = suffix.strip_prefix("bal") {
+            let base = base.parse::<i32>().ok()?;
+            return balanced::is_valid_base(base).then_some(Self::Balanced(base));
+        }
+
+        if suffix.starts_with('-') {
+            let base = suffix.parse::<i32>().ok()?;
+            return negabase::is_valid_base(base).then_some(Self::Negative(base));
+        }
+
+        alphabets::from_suffix(suffix).map(|(base, alphabet)| Self::Positional(base, alphabet))
+    }
+
+    /// Reads a number written in the system. Positional numbers may be complex, like `3+4i`,
+    /// if `complex_literal` is set.
+    fn parse(&self, num: &str, complex_literal: bool) -> Result<ComplexRational, ValidErrorKind> {
+        self.parse_digits(num, complex_literal).map_err(|_| self.invalid_number_error(num))
+    }
+
+    /// The error for a number that isn't written correctly in the system.
+    /// A binary-coded decimal points at the nibble that codes no digit.
+    fn invalid_number_error(&self, num: &str) -> ValidErrorKind {
+        match self {
+            Self::Zeckendorf(_) => ValidErrorKind::InvalidZeckendorfNumber,
+            Self::Decimal(code) => match code.parse_nibbles(num).ok().and_then(|nibbles| code.find_invalid(&nibbles)) {
+                Some((position, nibble)) => ValidErrorKind::InvalidBcdNibble(nibble, position),
+                None => ValidErrorKind::InvalidInitialNumber,
+            },
+            _ => ValidErrorKind::InvalidInitialNumber,
+        }
+    }
+
+    /// Checks that numbers in the system are bit patterns, so a bare `0x` literal is read as one.
+    fn is_binary_format(&self) -> bool {
+        matches!(self, Self::Float(_) | Self::FixedPoint(_) | Self::Decimal(_))
+    }
+
+    fn parse_digits(&self, num: &str, complex_literal: bool) -> Result<ComplexRational, ErrorKind> {
+        let number = match self {
+            Self::Positional(base, alphabet) if complex_literal => return complex::parse_literal(num, *base, alphabet),
+            Self::Positional(base, alphabet) | Self::Scientific(base, alphabet) => converting::parse_number(num, *base, alphabet)?,
+            Self::Balanced(base) => balanced::parse(num, *base)?,
+            Self::Negative(base) => negabase::parse(num, *base)?,
+            Self::Complex(base) => return complex::parse(num, *base),
+            Self::Factoradic => BigRational::from(factoradic::parse(num)?),
+            Self::Permutation(_) => {
+                let elements = num.split(',')
+                    .map(|element| element.parse::<usize>().map_err(|_| ErrorKind::InvalidData))
+                    .collect::<Result<Vec<_>, _>>()?;
+                BigRational::from(factoradic::rank(&elements).ok_or(ErrorKind::InvalidData)?)
+            }
+            Self::Zeckendorf(false) => BigRational::from(zeckendorf::parse(num)?),
+            Self::Zeckendorf(true) => BigRational::from(zeckendorf::parse_code(num)?),
+            Self::Roman(strict) => BigRational::from(roman::parse(num, *strict)?),
+            Self::Bijective(base, letters) => BigRational::from(bijective::parse(num, *base, *letters)?),
+            Self::MixedRadix(system) => system.parse(num)?,
+            Self::Float(format) => match format.decode(&format.parse_bits(num)?) {
+                (_, FloatValue::Finite(value)) => value,
+                _ => return Err(ErrorKind::InvalidData),
+            },
+            Self::FixedPoint(format) => format.value(&format.parse_raw(num)?),
+            Self::Decimal(code) => BigRational::from(code.decode(&code.parse_nibbles(num)?)?),
+            Self::Gray(base) => BigRational::from(gray::decode(num, *base)?),
+        };
+
+        Ok(ComplexRational::from(number))
+    }
+
+    /// Writes the number in the system. Fails if the number is complex and the system
+    /// can't write complex numbers, or if the system only writes some whole numbers.
+    /// Positional digits are cut with `rounding`.
+    fn format(
+        &self,
+        number: ComplexRational,
+        mantissa_length: Option<i32>,
+        rounding: RoundingMode,
+    ) -> Result<String, ValidErrorKind> {
+        let (real, imaginary) = (&number.re, &number.im);
+
+        match self {
+            Self::Positional(base, alphabet) if imaginary.is_zero() => {
+                let (expansion, ordering) = write_positional(real.clone(), *base, alphabet, mantissa_length, rounding);
+                Ok(describe_rounding(describe_expansion(expansion), ordering))
+            }
+            Self::Positional(base, alphabet) => {
+                let sign = if imaginary.is_negative() { "-" } else if real.is_zero() { "" } else { "+" };
+                let imaginary = write_positional(imaginary.abs(), *base, alphabet, mantissa_length, rounding).0.result;
+
+                if real.is_zero() {
+                    Ok(format!("{}{}i", sign, imaginary))
+                } else {
+                    let real = write_positional(real.clone(), *base, alphabet, mantissa_length, rounding).0.result;
+                    Ok(format!("{}{}{}i", real, sign, imaginary))
+                }
+            }
+            Self::Complex(base) => Ok(complex::format(&number, *base, mantissa_length)),
+            _ if !imaginary.is_zero() => Err(ValidErrorKind::InvalidConditionBase),
+            Self::Balanced(base) => Ok(balanced::format(real, *base, mantissa_length)),
+            Self::Negative(base) => Ok(negabase::format(real, *base, mantissa_length)),
+            Self::Factoradic => Ok(factoradic::format(&whole(real)?)),
+            Self::Permutation(Some(elements)) if *elements > factoradic::MAX_ELEMENTS => Err(ValidErrorKind::InvalidCondition),
+            Self::Permutation(elements) => {
+                let code = factoradic::lehmer_code(&whole(real)?, *elements).ok_or(ValidErrorKind::UnrepresentableNumber)?;
+                let join = |elements: &[usize]| elements.iter().map(|element| element.to_string()).collect::<Vec<_>>().join(",");
+                Ok(format!("{}\nLehmer code: {}", join(&factoradic::permutation(&code)), join(&code)))
+            }
+            Self::Zeckendorf(false) => zeckendorf::format(&whole(real)?).ok_or(ValidErrorKind::UnrepresentableNumber),
+            Self::Zeckendorf(true) => zeckendorf::format_code(&whole(real)?).ok_or(ValidErrorKind::UnrepresentableNumber),
+            Self::Roman(_) => roman::format(&whole(real)?).ok_or(ValidErrorKind::UnrepresentableNumber),
+            Self::Bijective(base, letters) => {
+                bijective::format(&whole(real)?, *base, *letters).ok_or(ValidErrorKind::UnrepresentableNumber)
+            }
+            Self::MixedRadix(system) => {
+                let (result, ordering) = system.format(real, mantissa_length, rounding);
+                Ok(describe_rounding(result, (!real.is_integer()).then_some(ordering)))
+            }
+            Self::Scientific(base, alphabet) => {
+                let (result, ordering) = converting::format_scientific(real.clone(), *base, mantissa_length, rounding, alphabet);
+                Ok(describe_rounding(result, Some(ordering)))
+            }
+            Self::Float(format) => Ok(format.describe(&format.encode(real), Some(real))),
+            Self::FixedPoint(format) => {
+                let (raw, saturated) = format.encode(real);
+                Ok(format.describe(&raw, Some(real), saturated))
+            }
+            Self::Decimal(code) => {
+                let nibbles = code.encode(&whole(real)?).ok_or(ValidErrorKind::UnrepresentableNumber)?;
+                Ok(code.describe(&nibbles))
+            }
+            Self::Gray(base) => gray::encode(&whole(real)?, *base).ok_or(ValidErrorKind::UnrepresentableNumber),
+        }
+    }
+}
+
+/// Takes the number as a whole number for the systems that can't write fractions.
+fn whole(number: &BigRational) -> Result<BigInt, ValidErrorKind> {
+    if number.is_integer() { Ok(number.to_integer()) } else { Err(ValidErrorKind::UnrepresentableNumber) }
+}
+
+/// Writes a number in a positional system. Without an explicit mantissa length the exact expansion
+/// is written if it is short enough. Otherwise the mantissa is cut with `rounding`, and the ordering
+/// compares the written number with the original one.
+fn write_positional(
+    number: BigRational,
+    base: i32,
+    alphabet: &Alphabet,
+    mantissa_length: Option<i32>,
+    rounding: RoundingMode,
+) -> (Expansion, Option<Ordering>) {
+    if number.is_integer() {
+        let result = converting::format_whole(number.to_integer(), base, alphabet);
+        return (Expansion { result, pre_period_length: 0, period_length: 0 }, None);
+    }
+
+    if mantissa_length.is_none() {
+        if let Some(expansion) = converting::format_periodic(number.clone(), base, MAX_MANTISSA_LENGTH as usize, alphabet) {
+            return (expansion, None);
+        }
+    }
+
+    let (result, ordering) = converting::format_rounded(number, base, mantissa_length, rounding, alphabet);
+    (Expansion { result, pre_period_length: 0, period_length: 0 }, Some(ordering))
+}
+
+/// Writes an exact expansion, telling the lengths of its parts if it repeats.
+fn describe_expansion(expansion: Expansion) -> String {
+    if expansion.period_length > 0 {
+        format!(
+            "{}\nPre-period length: {}, period length: {}",
+            expansion.result, expansion.pre_period_length, expansion.period_length,
+        )
+    } else {
+        expansion.result
+    }
+}
+
+/// Tells whether a number written with a cut mantissa is exact or which way it was rounded.
+fn describe_rounding(result: String, ordering: Option<Ordering>) -> String {
+    match ordering {
+        Some(Ordering::Less) => format!("{}\nRounded down", result),
+        Some(Ordering::Equal) => format!("{}\nExact", result),
+        Some(Ordering::Greater) => format!("{}\nRounded up", result),
+        None => result,
+    }
+}
+
+/// Writes a bit pattern in the base. Bases that are powers of two get leading zeros
+/// to show every bit of the integer.
+fn write_pattern(pattern: BigUint, width: u32, base: i32, alphabet: &Alphabet) -> String {
+    let digits = converting::format_whole(BigInt::from(pattern), base, alphabet);
+    if base.count_ones() != 1 { return digits }
+
+    let length = width.div_ceil(base.trailing_zeros()) as usize;
+    let padding = length.saturating_sub(digits.chars().count());
+    alphabet.digit(0).to_string().repeat(padding) + &digits
+}
+
+/// Splits a `0x`, `0o` or `0b` prefix off a number sent without a number system,
+/// returning the digits and the base the prefix stands for.
+fn split_literal_prefix(num: &str) -> Option<(&str, &'static str)> {
+    let base = match num.get(..2)? {
+        "0x" | "0X" => "16",
+        "0o" | "0O" => "8",
+        "0b" | "0B" => "2",
+        _ => return None,
+    };
+
+    Some((&num[2..], base))
+}
+
+/// Picks the error for a number system suffix that `alphabets::from_suffix` didn't accept.
+fn suffix_error(suffix: &str, otherwise: ValidErrorKind) -> ValidErrorKind {
+    if suffix.starts_with('{') { ValidErrorKind::InvalidAlphabet } else { otherwise }
 }
 
 #[derive(PartialEq)]
@@ -140,6 +915,17 @@ pub enum ValidErrorKind {
     InvalidInitialNumber,
     InvalidConditionBase,
     InvalidCondition,
+    InvalidAlphabet,
+    NumberTooLong,
+    ExplanationTooLong,
+    UnrepresentableNumber,
+    InvalidZeckendorfNumber,
+    Overflow,
+    /// The nibble that codes no digit and the position of its digit.
+    InvalidBcdNibble(u8, usize),
+    InvalidExpression,
+    DivisionByZero,
+    MissingWidth,
     UnknownError,
 }
 
@@ -162,6 +948,39 @@ pub mod error_messages {
     pub const INVALID_CONDITION_MESSAGE: &str =
         "Invalid condition";
 
+    pub const INVALID_ALPHABET_MESSAGE: &str =
+        "Invalid alphabet. Digits must be unique, there must be at least two of them, \
+        and they can't be spaces or any of - . ( ) _ { } [ ] >";
+
+    pub const NUMBER_TOO_LONG_MESSAGE: &str =
+        "Initial number is too long. It must not exceed 4096 digits";
+
+    pub const EXPLANATION_TOO_LONG_MESSAGE: &str =
+        "This conversion is too long to explain in one message. Try a shorter number or fewer digits after the point";
+
+    pub const UNREPRESENTABLE_NUMBER_MESSAGE: &str =
+        "This number can't be written in the desired number system";
+
+    pub const OVERFLOW_MESSAGE: &str =
+        "The number doesn't fit in an integer of this width";
+
+    pub const INVALID_BCD_NIBBLE_MESSAGE: &str =
+        "Invalid binary-coded decimal. This nibble doesn't code a decimal digit:";
+
+    pub const INVALID_ZECKENDORF_NUMBER_MESSAGE: &str =
+        "Invalid Zeckendorf number. It must be made of 0s and 1s with no two 1s in a row, \
+        and a Fibonacci code must also end with 11";
+
+    pub const INVALID_EXPRESSION_MESSAGE: &str =
+        "Invalid expression. Numbers go between operators and parentheses: (FF_16 + 1011_2) * 7_8 > 10. \
+        Powers take whole exponents, and // and % take real numbers";
+
+    pub const DIVISION_BY_ZERO_MESSAGE: &str =
+        "Division by zero";
+
+    pub const MISSING_WIDTH_MESSAGE: &str =
+        "Rotations and bit reversal need an integer width: 0x81 rol 1_10 u8 > 2";
+
     pub const UNKNOWN_ERROR_MESSAGE: &str =
         "An unexpected error occured...\n\
         Your last message will be sent to the developer, so he can fix it\n\
@@ -1,148 +1,2294 @@
 pub mod converting {
+    use std::cmp::Ordering;
+    use std::collections::HashMap;
     use std::io::ErrorKind;
-
-    // This is a mask that represents all possible digits in up to 50 decimal system
-    pub const DIGITMASK: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+    use std::ops::Neg;
+    use num_bigint::{BigInt, BigUint};
+    use num_integer::Integer;
+    use num_rational::BigRational;
+    use num_traits::{One, Pow, Signed, ToPrimitive, Zero};
+    use super::alphabets::{Alphabet, STANDARD};
 
     pub fn any_whole_to_decimal(
         num: &str,
-        num_base: i128,
+        num_base: i32,
+        alphabet: &Alphabet,
     ) -> Result<String, ErrorKind> {
-        let mut result: i128 = 0;
-        let len = num.len();
+        Ok(parse_whole(num, num_base, alphabet)?.to_string())
+    }
 
-        for (index, symb) in num.char_indices() {
-            if let Some(dig_index) = DIGITMASK.find(symb) {
-                result += dig_index as i128 * num_base.pow((len - index - 1) as u32);
-            } else {
-                return Err(ErrorKind::InvalidData);
-            }
-        }
+    pub fn decimal_whole_to_any(
+        num: &str,
+        base_to: i32,
+        alphabet: &Alphabet,
+        steps: Option<&mut Steps>,
+    ) -> Result<String, ErrorKind> {
+        let num =
+            if let Ok(num) = num.parse::<BigInt>() { num }
+            else { return Err(ErrorKind::InvalidData); };
 
-        Ok(result.to_string())
+        Ok(write_whole(num, base_to, alphabet, steps))
     }
 
-    pub fn any_fractional_to_decimal(
+    /// Converts a whole number, recording the steps in `steps` if it is given.
+    pub fn any_whole_to_any(
         num: &str,
         num_base: i32,
+        base_to: i32,
+        alphabet_from: &Alphabet,
+        alphabet_to: &Alphabet,
+        mut steps: Option<&mut Steps>,
     ) -> Result<String, ErrorKind> {
-        let mut num = num.to_owned();
-        let dot: usize =
-            if let Some(dot_index) = num.find('.') {
-                num.remove(dot_index);
-                dot_index
-            } else {
-                return Err(ErrorKind::InvalidData);
+        let integer_decimal = if let Ok(number) = any_whole_to_decimal(num, num_base, alphabet_from) {
+            number
+        } else {
+            return Err(ErrorKind::InvalidData);
+        };
+
+        if let Some(steps) = steps.as_deref_mut() {
+            let value = integer_decimal.parse::<BigInt>().map_err(|_| ErrorKind::InvalidData)?;
+            steps.record_expansion(num, BigRational::from_integer(value), alphabet_from)?;
+        }
+
+        decimal_whole_to_any(
+            &integer_decimal,
+            base_to,
+            alphabet_to,
+            steps,
+        )
+    }
+
+    /// Converts a number with a fractional part, cutting the mantissa with the given rounding mode.
+    /// Also tells whether the written number is equal to, greater or less than the original one.
+    #[allow(clippy::too_many_arguments)]
+    pub fn any_fractional_to_any_rounded(
+        num: &str,
+        num_base: i32,
+        base_to: i32,
+        mantissa_length: Option<i32>,
+        rounding: RoundingMode,
+        alphabet_from: &Alphabet,
+        alphabet_to: &Alphabet,
+        mut steps: Option<&mut Steps>,
+    ) -> Result<(String, Ordering), ErrorKind> {
+        let fraction = parse_fractional(num, num_base, alphabet_from)?;
+        if let Some(steps) = steps.as_deref_mut() {
+            steps.record_expansion(num, fraction.clone(), alphabet_from)?;
+        }
+
+        Ok(write_rounded(fraction, base_to, mantissa_length, rounding, alphabet_to, steps))
+    }
+
+    /// Converts a number with a fractional part and finds the repeating block of its expansion.
+    /// Returns `None` if the pre-period and the period together don't fit in `max_length` digits.
+    pub fn any_fractional_to_any_periodic(
+        num: &str,
+        num_base: i32,
+        base_to: i32,
+        max_length: usize,
+        alphabet_from: &Alphabet,
+        alphabet_to: &Alphabet,
+        mut steps: Option<&mut Steps>,
+    ) -> Result<Option<Expansion>, ErrorKind> {
+        let fraction = parse_fractional(num, num_base, alphabet_from)?;
+        if let Some(steps) = steps.as_deref_mut() {
+            steps.record_expansion(num, fraction.clone(), alphabet_from)?;
+        }
+
+        Ok(write_periodic(fraction, base_to, max_length, alphabet_to, steps))
+    }
+
+    /// Marks the exponent of a number in scientific notation, unless the mark is a digit of the base.
+    /// `e` and `p` read like in `6.02e23` and `1.01p+5`, and `^` works in any base.
+    const EXPONENT_MARKS: [char; 3] = ['e', 'p', '^'];
+
+    /// The greatest exponent a number may have, so that it doesn't take forever to write out.
+    const MAX_EXPONENT: u32 = 4096;
+
+    /// Reads a whole number or a number with a fractional part written in `num_base`.
+    /// The number may have a decimal exponent of the base: `1.01p+5` is 1.01 × 2^5 in binary.
+    /// Only one exponent is allowed, so the mantissa itself can't have one.
+    pub fn parse_number(num: &str, num_base: i32, alphabet: &Alphabet) -> Result<BigRational, ErrorKind> {
+        if let Some((mantissa, exponent)) = split_exponent(num, num_base, alphabet) {
+            let exponent = exponent.parse::<i32>().map_err(|_| ErrorKind::InvalidData)?;
+            if exponent.unsigned_abs() > MAX_EXPONENT { return Err(ErrorKind::InvalidData) }
+
+            let mantissa = parse_mantissa(mantissa, num_base, alphabet)?;
+            let scale = BigRational::from(BigInt::from(num_base)).pow(exponent);
+            return Ok(mantissa * scale);
+        }
+
+        parse_mantissa(num, num_base, alphabet)
+    }
+
+    /// Reads a whole number or a number with a fractional part, without an exponent.
+    fn parse_mantissa(num: &str, num_base: i32, alphabet: &Alphabet) -> Result<BigRational, ErrorKind> {
+        if num.contains('.') {
+            parse_fractional(num, num_base, alphabet)
+        } else {
+            Ok(BigRational::from_integer(parse_whole(num, num_base, alphabet)?))
+        }
+    }
+
+    /// Splits a number in scientific notation into its mantissa and exponent.
+    /// Returns `None` if the number has no exponent.
+    pub fn split_exponent<'a>(num: &'a str, num_base: i32, alphabet: &Alphabet) -> Option<(&'a str, &'a str)> {
+        let (position, _) = num.char_indices().rev().find(|&(_, symb)| is_exponent_mark(symb, num_base, alphabet))?;
+        let (mantissa, exponent) = (&num[..position], &num[position + 1..]);
+
+        let digits = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+        if mantissa.is_empty() || digits.is_empty() || !digits.chars().all(|digit| digit.is_ascii_digit()) {
+            return None;
+        }
+
+        Some((mantissa, exponent))
+    }
+
+    /// Writes a number in normalized scientific notation, with one non-zero digit before the point
+    /// and a decimal exponent of `base_to`: 6.02 × 10^23 is `7.F7A7C42A34p+19` in hexadecimal.
+    /// The mantissa is cut after `mantissa_length` digits (10 by default).
+    /// The mantissa is rounded with `rounding`, and the ordering compares the written number with the original one.
+    pub fn format_scientific(
+        num: BigRational,
+        base_to: i32,
+        mantissa_length: Option<i32>,
+        rounding: RoundingMode,
+        alphabet: &Alphabet,
+    ) -> (String, Ordering) {
+        if num.is_zero() { return (alphabet.digit(0).to_string(), Ordering::Equal) }
+
+        // The bit lengths give a close guess of the exponent, which is then corrected. //
+        let bits = num.numer().bits() as f64 - num.denom().bits() as f64;
+        let mut exponent = (bits / (base_to as f64).log2()).floor() as i32;
+        let base = BigRational::from(BigInt::from(base_to));
+        let mut mantissa = num.abs() / base.clone().pow(exponent);
+
+        while mantissa >= base {
+            mantissa /= base.clone();
+            exponent += 1;
+        }
+        while mantissa < BigRational::one() {
+            mantissa *= base.clone();
+            exponent -= 1;
+        }
+
+        let mark = EXPONENT_MARKS.into_iter()
+            .find(|&mark| is_exponent_mark(mark, base_to, alphabet))
+            .unwrap_or('^');
+        let mut mantissa = with_sign(num.is_negative(), mantissa);
+        let mut rounded = format_rounded(mantissa.clone(), base_to, mantissa_length, rounding, alphabet);
+
+        // Rounding up can carry into a second whole digit, as in 9.99 to 10.0. //
+        let whole_length = |result: &str| result.trim_start_matches('-').split('.').next().unwrap_or("").chars().count();
+        if whole_length(&rounded.0) > 1 {
+            mantissa /= base;
+            exponent += 1;
+            rounded = format_rounded(mantissa, base_to, mantissa_length, rounding, alphabet);
+        }
+
+        (format!("{}{}{:+}", rounded.0, mark, exponent), rounded.1)
+    }
+
+    /// Longest exact decimal value `write_decimal` shows. Longer values are shown in scientific notation.
+    const MAX_EXACT_LENGTH: usize = 100;
+
+    /// Digits of the mantissa when a value is too long to be shown exactly.
+    const APPROXIMATE_LENGTH: i32 = 40;
+
+    /// Writes a value exactly in decimal if it is short enough, and approximately otherwise.
+    pub fn write_decimal(value: BigRational) -> String {
+        match format_periodic(value.clone(), 10, MAX_EXACT_LENGTH, &STANDARD) {
+            Some(expansion) if expansion.result.len() <= MAX_EXACT_LENGTH => expansion.result,
+            _ => format!("≈{}", format_scientific(value, 10, Some(APPROXIMATE_LENGTH), RoundingMode::TowardZero, &STANDARD).0),
+        }
+    }
+
+    /// Rounds to the nearest whole number, ties to even.
+    pub fn round_half_even(value: &BigRational) -> BigInt {
+        let floor = value.floor();
+        let difference = value - &floor;
+        let half = BigRational::new(BigInt::one(), BigInt::from(2));
+        let floor = floor.to_integer();
+
+        if difference > half || (difference == half && floor.is_odd()) { floor + 1 } else { floor }
+    }
+
+    /// How the last shown digit is chosen when the mantissa is cut.
+    #[derive(Clone, Copy, Debug, Default, PartialEq)]
+    pub enum RoundingMode {
+        /// To the nearest value, ties to the even digit.
+        HalfEven,
+        /// To the nearest value, ties away from zero.
+        HalfUp,
+        /// Drops the remaining digits.
+        #[default]
+        TowardZero,
+        /// Toward +∞.
+        Ceiling,
+        /// Toward −∞.
+        Floor,
+    }
+
+    impl RoundingMode {
+        pub const ALL: [RoundingMode; 5] = [
+            RoundingMode::HalfEven,
+            RoundingMode::HalfUp,
+            RoundingMode::TowardZero,
+            RoundingMode::Ceiling,
+            RoundingMode::Floor,
+        ];
+
+        pub fn name(self) -> &'static str {
+            match self {
+                RoundingMode::HalfEven => "halfeven",
+                RoundingMode::HalfUp => "halfup",
+                RoundingMode::TowardZero => "truncate",
+                RoundingMode::Ceiling => "ceiling",
+                RoundingMode::Floor => "floor",
+            }
+        }
+
+        pub fn from_name(name: &str) -> Option<Self> {
+            let name = name.to_lowercase();
+            Self::ALL.into_iter().find(|mode| mode.name() == name)
+        }
+
+        /// Rounds to an integer.
+        pub fn round(self, value: &BigRational) -> BigInt {
+            match self {
+                RoundingMode::HalfEven => round_half_even(value),
+                RoundingMode::HalfUp => value.round().to_integer(),
+                RoundingMode::TowardZero => value.trunc().to_integer(),
+                RoundingMode::Ceiling => value.ceil().to_integer(),
+                RoundingMode::Floor => value.floor().to_integer(),
+            }
+        }
+    }
+
+    fn is_exponent_mark(symb: char, base: i32, alphabet: &Alphabet) -> bool {
+        EXPONENT_MARKS.contains(&symb.to_ascii_lowercase())
+            && alphabet.value_of(symb).is_none_or(|value| value as i32 >= base)
+    }
+
+    /// Exact positional expansion of a fraction, e.g. `0.0(0011)` for 0.1 in binary.
+    pub struct Expansion {
+        pub result: String,
+        pub pre_period_length: usize,
+        pub period_length: usize,
+    }
+
+    /// One step of the repeated division of the whole part by the new base.
+    pub struct DivisionStep {
+        pub dividend: BigInt,
+        pub quotient: BigInt,
+        pub remainder: usize,
+    }
+
+    /// One step of the repeated multiplication of the fractional part by the new base.
+    pub struct MultiplicationStep {
+        pub fraction: BigRational,
+        pub product: BigRational,
+        pub digit: usize,
+    }
+
+    /// Every step of a conversion between positional systems, recorded while the conversion runs
+    /// to explain it.
+    pub struct Steps {
+        /// The number as it was written, without the sign.
+        pub number: String,
+        pub negative: bool,
+        pub num_base: i32,
+        pub base_to: i32,
+        pub alphabet_to: Alphabet,
+        /// Digit values and the powers of `num_base` they stand at, from the most significant one.
+        /// Empty for numbers with a repeating block.
+        pub terms: Vec<(usize, i32)>,
+        pub value: BigRational,
+        pub divisions: Vec<DivisionStep>,
+        pub multiplications: Vec<MultiplicationStep>,
+        /// The step whose fraction comes up again once the multiplications stop,
+        /// so the digits from it on repeat.
+        pub period_start: Option<usize>,
+        /// How the fractional part was cut before its digits ran out, if it was.
+        pub rounding: Option<RoundingMode>,
+    }
+
+    impl Steps {
+        /// Starts recording a conversion from `num_base` to `base_to`.
+        pub fn new(num_base: i32, base_to: i32, alphabet_to: &Alphabet) -> Self {
+            Self {
+                number: String::new(),
+                negative: false,
+                num_base,
+                base_to,
+                alphabet_to: alphabet_to.clone(),
+                terms: Vec::new(),
+                value: BigRational::zero(),
+                divisions: Vec::new(),
+                multiplications: Vec::new(),
+                period_start: None,
+                rounding: None,
+            }
+        }
+
+        /// Records the positional expansion of `num`, which was read as `value`.
+        fn record_expansion(&mut self, num: &str, value: BigRational, alphabet_from: &Alphabet) -> Result<(), ErrorKind> {
+            let (negative, number) = split_sign(num);
+
+            self.terms.clear();
+            if !number.contains('(') {
+                let whole_length = number.find('.').unwrap_or(number.len()) as i32;
+                let digits = number.chars().filter(|&digit| digit != '.');
+                for (position, digit) in digits.enumerate() {
+                    let power = whole_length - 1 - position as i32;
+                    self.terms.push((alphabet_from.value_of(digit).ok_or(ErrorKind::InvalidData)?, power));
+                }
+            }
+
+            self.number = number.to_owned();
+            self.negative = negative;
+            self.value = value;
+            Ok(())
+        }
+
+        /// A lower bound of the length of the explanation, found without writing it.
+        /// Every row of a table has three cells and two ` | ` between them.
+        pub fn min_length(&self) -> usize {
+            (self.divisions.len() + self.multiplications.len()) * "0 | 0 | 0\n".len()
+        }
+
+        /// Writes the steps as the expansion sum followed by the division and multiplication tables,
+        /// ending with the converted number as it is written in `result`.
+        pub fn describe(&self, result: &str) -> String {
+            let alphabet_to = &self.alphabet_to;
+            let sign = if self.negative { "-" } else { "" };
+            let digit = |value: usize| {
+                let symbol = alphabet_to.digit(value);
+                if symbol.to_digit(10) == Some(value as u32) { value.to_string() } else { format!("{} ({})", value, symbol) }
             };
-        let mut result: f64 = 0.0;
+            let mut lines = Vec::new();
 
-        for (index, symb) in num[..dot].char_indices()  {
-            if let Some(dig) = DIGITMASK.find(symb) {
-                result += dig as f64 * num_base.pow((dot - index - 1) as u32) as f64;
+            if self.terms.is_empty() {
+                lines.push(format!("{}{} is the fraction {}", sign, self.number, self.value));
             } else {
-                return Err(ErrorKind::InvalidData);
+                let sum = self.terms.iter()
+                    .map(|(value, power)| format!("{}·{}{}", value, self.num_base, superscript(*power)))
+                    .collect::<Vec<_>>()
+                    .join(" + ");
+                let sum = if self.negative { format!("-({})", sum) } else { sum };
+                lines.push("Positional expansion:".to_owned());
+                lines.push(format!("{}{} = {}", sign, self.number, sum));
+                lines.push(format!("= {}", write_decimal(self.value.clone())));
             }
-        }
 
-        for (index, symb) in num[dot..].char_indices()  {
-            if let Some(dig) = DIGITMASK.find(symb) {
-                result += dig as f64 / num_base.pow(index as u32 + 1) as f64;
+            lines.push(String::new());
+            if self.divisions.is_empty() {
+                lines.push("The whole part is 0".to_owned());
             } else {
-                return Err(ErrorKind::InvalidData);
+                lines.push(format!("Whole part, repeated division by {}:", self.base_to));
+                let rows = self.divisions.iter()
+                    .map(|step| [step.dividend.to_string(), step.quotient.to_string(), digit(step.remainder)])
+                    .collect::<Vec<_>>();
+                lines.push(table(["Dividend", "Quotient", "Remainder"], rows));
+                let digits: String = self.divisions.iter().rev().map(|step| alphabet_to.digit(step.remainder)).collect();
+                lines.push(format!("Remainders from the bottom up: {}", digits));
+            }
+
+            if !self.multiplications.is_empty() {
+                lines.push(String::new());
+                lines.push(format!("Fractional part, repeated multiplication by {}:", self.base_to));
+                let rows = self.multiplications.iter()
+                    .map(|step| [write_decimal(step.fraction.clone()), write_decimal(step.product.clone()), digit(step.digit)])
+                    .collect::<Vec<_>>();
+                lines.push(table(["Fraction", &format!("× {}", self.base_to), "Digit"], rows));
+                let digits: String = self.multiplications.iter().map(|step| alphabet_to.digit(step.digit)).collect();
+                lines.push(format!("Whole parts from the top down: {}", digits));
+                if let Some(rounding) = self.rounding {
+                    lines.push(format!(
+                        "The digits go on, so they are cut after {} digits (rounding mode: {})",
+                        self.multiplications.len(), rounding.name(),
+                    ));
+                } else if let Some(start) = self.period_start {
+                    lines.push(format!(
+                        "The fraction {} comes up again, so the last {} digits repeat",
+                        write_decimal(self.multiplications[start].fraction.clone()), self.multiplications.len() - start,
+                    ));
+                }
+            }
+
+            lines.push(String::new());
+            lines.push(format!("Result: {}", result));
+            lines.join("\n")
+        }
+    }
+
+    /// Lines up the columns of a table, with a rule under the header.
+    fn table(header: [&str; 3], rows: Vec<[String; 3]>) -> String {
+        let mut widths = header.map(|title| title.chars().count());
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
             }
         }
 
-        Ok(result.to_string())
+        let line = |cells: [&str; 3]| {
+            let cells = cells.iter().zip(widths).map(|(cell, width)| format!("{:<1$}", cell, width));
+            cells.collect::<Vec<_>>().join(" | ").trim_end().to_owned()
+        };
+        let mut lines = vec![line(header), widths.map(|width| "-".repeat(width)).join("-+-")];
+        lines.extend(rows.iter().map(|row| line([&row[0], &row[1], &row[2]])));
+        lines.join("\n")
     }
 
-    pub fn decimal_whole_to_any(
-        num: &str,
-        base_to: i32,
-    ) -> Result<String, ErrorKind> {
-        let mut num =
-            if let Ok(num) = num.parse::<i128>() { num }
-            else { return Err(ErrorKind::InvalidData); };
-        let mut result = String::new();
+    /// Writes an exponent with superscript digits: 2⁻³.
+    fn superscript(power: i32) -> String {
+        power.to_string().chars().map(|symb| match symb {
+            '-' => '⁻',
+            _ => ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'][symb.to_digit(10).unwrap() as usize],
+        }).collect()
+    }
+
+    /// Reads a whole number written in `num_base` into an arbitrary-precision integer.
+    /// The number may start with a minus sign.
+    pub fn parse_whole(num: &str, num_base: i32, alphabet: &Alphabet) -> Result<BigInt, ErrorKind> {
+        let (negative, num) = split_sign(num);
+        if num.is_empty() { return Err(ErrorKind::InvalidData) }
+
+        let mut result = BigUint::zero();
 
-        while num > 0 {
-            result.insert(0, DIGITMASK.chars().nth((num % base_to as i128) as usize).unwrap());
-            num /= base_to as i128;
+        for symb in num.chars() {
+            match alphabet.value_of(symb) {
+                Some(dig_index) if (dig_index as i32) < num_base => {
+                    result = result * num_base as u32 + dig_index as u32;
+                }
+                _ => return Err(ErrorKind::InvalidData),
+            }
         }
 
-        Ok(result)
+        Ok(with_sign(negative, BigInt::from(result)))
     }
 
-    pub fn decimal_fractional_to_any(
-        num: &str,
-        base_to: i32,
-        mantissa_length: Option<i32>
-    ) -> Result<String, ErrorKind> {
-        let whole_num_part = (num.parse::<f64>().unwrap() as i32).to_string();
-        let converted_whole_part = decimal_whole_to_any(&whole_num_part, base_to).unwrap();
-        let mut fractional_part = fractional_part_float(num.parse::<f64>().unwrap());
-        let mut converted_fractional_part = String::new();
+    /// Writes an arbitrary-precision integer in `base_to` using repeated division.
+    pub fn format_whole(num: BigInt, base_to: i32, alphabet: &Alphabet) -> String {
+        write_whole(num, base_to, alphabet, None)
+    }
 
-        let mut length: i32 = 10;
-        if let Some(len) = mantissa_length { length = len }
+    /// Writes an integer like `format_whole`, recording every division in `steps`.
+    fn write_whole(num: BigInt, base_to: i32, alphabet: &Alphabet, mut steps: Option<&mut Steps>) -> String {
+        let mut magnitude = num.magnitude().clone();
+        let mut result = String::new();
 
-        for _ in 0..length {
-            let left_side = (fractional_part * base_to as f64) as i32;
-            converted_fractional_part
-                .push(DIGITMASK.chars().nth(left_side as usize).unwrap());
-            fractional_part = fractional_part_float(fractional_part * base_to as f64);
+        while !magnitude.is_zero() {
+            let (quotient, remainder) = magnitude.div_rem(&BigUint::from(base_to as u32));
+            let digit = remainder.to_usize().unwrap();
+            if let Some(steps) = steps.as_deref_mut() {
+                steps.divisions.push(DivisionStep { dividend: magnitude.into(), quotient: quotient.clone().into(), remainder: digit });
+            }
+            result.push(alphabet.digit(digit));
+            magnitude = quotient;
         }
 
-        let result = format!("{}.{}", converted_whole_part, converted_fractional_part);
-        let mut result = result.trim_end_matches('0').to_owned();
-        if result.find('.') == Some(0) { result.insert(0, '0') }
+        if result.is_empty() { result.push(alphabet.digit(0)) }
+        if num.is_negative() { result.push('-') }
 
-        Ok(result)
+        result.chars().rev().collect()
     }
 
-    pub fn any_whole_to_any(
-        num: &str,
-        num_base: i32,
-        base_to: i32,
-    ) -> Result<String, ErrorKind> {
-        let integer_decimal = if let Ok(number) = any_whole_to_decimal(num, num_base.into()) {
-            number
+    /// Reads a number with a fractional part written in `num_base` into an exact fraction.
+    /// The repeating block of the fractional part may be put in parentheses.
+    ///
+    /// **Example:**
+    /// ```
+    /// parse_fractional("-10.1", 2) // -5/2
+    /// parse_fractional("0.1(6)", 10) // 1/6
+    /// ```
+    pub fn parse_fractional(num: &str, num_base: i32, alphabet: &Alphabet) -> Result<BigRational, ErrorKind> {
+        let (negative, num) = split_sign(num);
+        let dot = if let Some(dot_index) = num.find('.') {
+            dot_index
         } else {
             return Err(ErrorKind::InvalidData);
         };
 
-        decimal_whole_to_any(
-            &integer_decimal,
-            base_to
-        )
+        let (num, period) = match num.find('(') {
+            Some(open) if open > dot && num.ends_with(')') => (&num[..open], &num[open + 1..num.len() - 1]),
+            Some(_) => return Err(ErrorKind::InvalidData),
+            None => (num, ""),
+        };
+
+        let digits = format!("{}{}", &num[..dot], &num[dot + 1..]);
+        let fractional_length = num[dot + 1..].chars().count() as u32;
+        let scale = BigInt::from(num_base).pow(fractional_length);
+
+        let mut fraction = BigRational::new(parse_whole(&digits, num_base, alphabet)?, scale.clone());
+
+        if !period.is_empty() {
+            // 0.(d₁…dₖ) is equal to d₁…dₖ / (baseᵏ - 1)
+            let repeated = BigInt::from(num_base).pow(period.chars().count() as u32) - 1;
+            fraction += BigRational::new(parse_whole(period, num_base, alphabet)?, repeated * scale);
+        }
+
+        Ok(with_sign(negative, fraction))
     }
 
-    pub fn any_fractional_to_any(
-        num: &str,
-        num_base: i32,
+    /// Writes an exact fraction in `base_to`, truncating it after `mantissa_length` digits
+    /// (10 by default) and dropping trailing zeros.
+    pub fn format_fractional(
+        num: BigRational,
         base_to: i32,
         mantissa_length: Option<i32>,
-    ) -> Result<String, ErrorKind> {
-        let fractional_decimal = if let Ok(number) = any_fractional_to_decimal(num, num_base) {
-            number
-        } else {
-            return Err(ErrorKind::InvalidData);
+        alphabet: &Alphabet,
+    ) -> String {
+        format_rounded(num, base_to, mantissa_length, RoundingMode::TowardZero, alphabet).0
+    }
+
+    /// Writes an exact fraction in `base_to` with `mantissa_length` digits (10 by default)
+    /// after the point, rounded with `rounding`, and drops trailing zeros.
+    /// The ordering compares the written number with the original one.
+    pub fn format_rounded(
+        num: BigRational,
+        base_to: i32,
+        mantissa_length: Option<i32>,
+        rounding: RoundingMode,
+        alphabet: &Alphabet,
+    ) -> (String, Ordering) {
+        write_rounded(num, base_to, mantissa_length, rounding, alphabet, None)
+    }
+
+    /// Writes a fraction like `format_rounded`, recording in `steps` the division of the whole part
+    /// and the multiplications that give the digits before the cut.
+    fn write_rounded(
+        num: BigRational,
+        base_to: i32,
+        mantissa_length: Option<i32>,
+        rounding: RoundingMode,
+        alphabet: &Alphabet,
+        mut steps: Option<&mut Steps>,
+    ) -> (String, Ordering) {
+        let length = mantissa_length.unwrap_or(10).max(0) as u32;
+        let scale = BigInt::from(base_to).pow(length);
+        let rounded = rounding.round(&(&num * &scale));
+        let ordering = BigRational::new(rounded.clone(), scale.clone()).cmp(&num);
+
+        let (whole_part, fractional_part) = rounded.abs().div_rem(&scale);
+        let zero = alphabet.digit(0);
+        let mut converted_fractional_part = String::new();
+        if length > 0 {
+            let digits = format_whole(fractional_part, base_to, alphabet);
+            let padding = length as usize - digits.chars().count();
+            converted_fractional_part = zero.to_string().repeat(padding) + &digits;
+        }
+
+        if let Some(steps) = steps.as_deref_mut() {
+            let mut fraction = num.abs().fract();
+            for _ in 0..length {
+                if fraction.is_zero() { break }
+
+                let product = &fraction * BigInt::from(base_to);
+                let digit = product.to_integer().to_usize().unwrap();
+                steps.multiplications.push(MultiplicationStep { fraction, product: product.clone(), digit });
+                fraction = product.fract();
+            }
+            steps.rounding = (!fraction.is_zero()).then_some(rounding);
+        }
+
+        let converted_fractional_part = converted_fractional_part.trim_end_matches(zero);
+        let mut result = write_whole(whole_part, base_to, alphabet, steps);
+        if !converted_fractional_part.is_empty() {
+            result = format!("{}.{}", result, converted_fractional_part);
+        }
+        if rounded.is_negative() {
+            result.insert(0, '-');
+        }
+
+        (result, ordering)
+    }
+
+    /// Writes an exact fraction in `base_to` by long division, remembering the remainders
+    /// to find where the digits start repeating.
+    pub fn format_periodic(
+        num: BigRational,
+        base_to: i32,
+        max_length: usize,
+        alphabet: &Alphabet,
+    ) -> Option<Expansion> {
+        write_periodic(num, base_to, max_length, alphabet, None)
+    }
+
+    /// Writes a fraction like `format_periodic`, recording in `steps` the division of the whole part
+    /// and every step of the long division, which is the multiplication of the fractional part.
+    /// Nothing is recorded if the expansion is too long.
+    fn write_periodic(
+        num: BigRational,
+        base_to: i32,
+        max_length: usize,
+        alphabet: &Alphabet,
+        mut steps: Option<&mut Steps>,
+    ) -> Option<Expansion> {
+        let denominator = num.denom().clone();
+        let mut remainder = num.abs().fract().numer().clone();
+        let mut remainders: HashMap<BigInt, usize> = HashMap::new();
+        let mut digits: Vec<char> = Vec::new();
+        let mut multiplications = Vec::new();
+
+        while !remainder.is_zero() && !remainders.contains_key(&remainder) {
+            if remainders.len() == max_length { return None }
+
+            remainders.insert(remainder.clone(), remainders.len());
+            let fraction = steps.is_some().then(|| BigRational::new(remainder.clone(), denominator.clone()));
+            remainder *= base_to;
+            let digit = (&remainder / &denominator).to_usize().unwrap();
+            if let Some(fraction) = fraction {
+                let product = BigRational::new(remainder.clone(), denominator.clone());
+                multiplications.push(MultiplicationStep { fraction, product, digit });
+            }
+            digits.push(alphabet.digit(digit));
+            remainder %= &denominator;
+        }
+
+        let pre_period_length = remainders.get(&remainder).copied().unwrap_or(digits.len());
+        let period_length = digits.len() - pre_period_length;
+
+        if let Some(steps) = steps.as_deref_mut() {
+            steps.multiplications = multiplications;
+            steps.period_start = (period_length > 0).then_some(pre_period_length);
+        }
+        let mut result = write_whole(num.abs().to_integer(), base_to, alphabet, steps);
+        if !digits.is_empty() {
+            result.push('.');
+            result.extend(&digits[..pre_period_length]);
+        }
+        if period_length > 0 {
+            result.push('(');
+            result.extend(&digits[pre_period_length..]);
+            result.push(')');
+        }
+        if num.is_negative() { result.insert(0, '-') }
+
+        Some(Expansion { result, pre_period_length, period_length })
+    }
+
+    /// Writes digit values given least significant first, with the last `length` of them
+    /// after the point. Leading zeros and trailing zeros after the point are dropped.
+    pub fn write_digits(digits: &[usize], length: usize, alphabet: &Alphabet) -> String {
+        let zero = alphabet.digit(0);
+        let mut fractional_part: String = digits[..length].iter().rev().map(|&digit| alphabet.digit(digit)).collect();
+        while fractional_part.ends_with(zero) { fractional_part.pop(); }
+
+        let whole_part: String = digits[length..].iter().rev().map(|&digit| alphabet.digit(digit)).collect();
+        let mut result = match whole_part.trim_start_matches(zero) {
+            "" => zero.to_string(),
+            whole_part => whole_part.to_owned(),
         };
 
-        decimal_fractional_to_any(
-            &fractional_decimal,
-            base_to,
-            mantissa_length,
-        )
+        if !fractional_part.is_empty() {
+            result = format!("{}.{}", result, fractional_part);
+        }
+
+        result
     }
 
-    /// Returns the fractional part of a float number.
-    ///
-    /// **Example:**
-    /// ```
-    /// fractional_part_float(123.456) // 0.456
-    /// ```
-    fn fractional_part_float(num: f64) -> f64 {
-        let whole_part = num as i32 as f64;
-        num - whole_part
+    /// A mixed-radix system, where every position has its own radix. Fields are written in decimal
+    /// and separated by colons, and the leading field is unbounded: days, hours, minutes and seconds
+    /// are `[24,60,60]`, so 100000 seconds are `1:03:46:40`.
+    #[derive(Clone, PartialEq)]
+    pub struct MixedRadix {
+        /// Radices of the fields after the leading one, most significant first.
+        radices: Vec<BigInt>,
+    }
+
+    impl MixedRadix {
+        const SEPARATOR: char = ':';
+
+        /// The most radices a user can list.
+        const MAX_RADICES: usize = 64;
+
+        /// Reads a radix list like `[7,24,60,60]`, or one of the named systems:
+        /// `hms` for hours, minutes and seconds, `dhms` with days in front,
+        /// and `dms` for degrees, minutes and seconds of arc.
+        pub fn from_suffix(suffix: &str) -> Option<Self> {
+            let radices: Vec<u32> = match suffix {
+                "hms" | "dms" => vec![60, 60],
+                "dhms" => vec![24, 60, 60],
+                _ => suffix.strip_prefix('[')?.strip_suffix(']')?
+                    .split(',')
+                    .map(|radix| radix.parse::<u32>().ok().filter(|&radix| radix >= 2))
+                    .collect::<Option<_>>()?,
+            };
+            if radices.len() > Self::MAX_RADICES { return None }
+
+            Some(Self { radices: radices.into_iter().map(BigInt::from).collect() })
+        }
+
+        /// Reads a mixed-radix number. Missing leading fields are zero, so `46:40` in `[24,60,60]`
+        /// is 46 minutes and 40 seconds. Only the last field may have a fractional part.
+        pub fn parse(&self, num: &str) -> Result<BigRational, ErrorKind> {
+            let (negative, num) = split_sign(num);
+            let fields: Vec<&str> = num.split(Self::SEPARATOR).collect();
+            if fields.len() > self.radices.len() + 1 { return Err(ErrorKind::InvalidData) }
+
+            let radices = &self.radices[self.radices.len() + 1 - fields.len()..];
+            let mut result = BigRational::zero();
+
+            for (position, field) in fields.iter().enumerate() {
+                let last = position == fields.len() - 1;
+                if field.contains(['-', '(', ')']) || (!last && field.contains('.')) {
+                    return Err(ErrorKind::InvalidData);
+                }
+
+                let value = parse_number(field, 10, &STANDARD)?;
+                if position == 0 {
+                    result = value;
+                } else {
+                    let radix = BigRational::from(radices[position - 1].clone());
+                    if value >= radix { return Err(ErrorKind::InvalidData) }
+                    result = result * radix + value;
+                }
+            }
+
+            Ok(with_sign(negative, result))
+        }
+
+        /// Writes a number with every field, padding all but the leading one with zeros.
+        /// The fractional part goes to the last field, rounded with `rounding` to `mantissa_length` digits
+        /// (10 by default). The ordering compares the written number with the original one.
+        pub fn format(&self, num: &BigRational, mantissa_length: Option<i32>, rounding: RoundingMode) -> (String, Ordering) {
+            // Rounding comes first, so a carry goes on to the fields in front. //
+            let scale = BigInt::from(10).pow(mantissa_length.unwrap_or(10).max(0) as u32);
+            let rounded = BigRational::new(rounding.round(&(num * &scale)), scale);
+            let ordering = rounded.cmp(num);
+            let num = &rounded;
+
+            let mut whole = num.abs().to_integer();
+            let mut fields = Vec::new();
+
+            for radix in self.radices.iter().rev() {
+                let (quotient, remainder) = whole.div_rem(radix);
+                fields.push((remainder, (radix - 1u32).to_string().len()));
+                whole = quotient;
+            }
+            fields.push((whole, 0));
+            fields.reverse();
+
+            let last = fields.len() - 1;
+            let fields: Vec<String> = fields.into_iter().enumerate().map(|(position, (value, width))| {
+                let field = if position == last {
+                    format_fractional(BigRational::from(value) + num.abs().fract(), 10, mantissa_length, &STANDARD)
+                } else {
+                    value.to_string()
+                };
+
+                let whole_length = field.find('.').unwrap_or(field.len());
+                "0".repeat(width.saturating_sub(whole_length)) + &field
+            }).collect();
+
+            let mut result = fields.join(&Self::SEPARATOR.to_string());
+            if num.is_negative() && result.chars().any(|digit| digit.is_ascii_digit() && digit != '0') {
+                result.insert(0, '-');
+            }
+
+            (result, ordering)
+        }
+    }
+
+    /// Splits a leading minus sign off the number.
+    fn split_sign(num: &str) -> (bool, &str) {
+        match num.strip_prefix('-') {
+            Some(magnitude) => (true, magnitude),
+            None => (false, num),
+        }
+    }
+
+    /// Negates the number if the sign was split off it.
+    fn with_sign<T: Neg<Output = T>>(negative: bool, num: T) -> T {
+        if negative { -num } else { num }
+    }
+}
+
+/// IEEE 754 binary floating-point formats and the low-precision formats built like them.
+/// A float is a sign bit, a biased exponent and a fraction; the exponent of all zeros marks
+/// subnormal numbers, and the exponent of all ones marks infinities and NaNs.
+pub mod ieee754 {
+    use std::io::ErrorKind;
+    use num_bigint::{BigInt, BigUint};
+    use num_rational::BigRational;
+    use num_traits::{One, Pow, Signed, ToPrimitive, Zero};
+    use super::converting;
+    use super::fixed_width;
+
+    /// A binary floating-point format, described by the sizes of its fields.
+    #[derive(Clone, Copy, PartialEq)]
+    pub struct FloatFormat {
+        pub name: &'static str,
+        exponent_bits: u32,
+        fraction_bits: u32,
+        /// Has no infinities, and only the pattern of all ones is a NaN,
+        /// so the exponent of all ones holds normal numbers.
+        finite_only: bool,
+        /// Values too great for the format become the greatest finite value instead of overflowing.
+        saturating: bool,
+    }
+
+    const fn ieee(name: &'static str, exponent_bits: u32, fraction_bits: u32) -> FloatFormat {
+        FloatFormat { name, exponent_bits, fraction_bits, finite_only: false, saturating: false }
+    }
+
+    pub const HALF: FloatFormat = ieee("f16", 5, 10);
+    pub const SINGLE: FloatFormat = ieee("f32", 8, 23);
+    pub const DOUBLE: FloatFormat = ieee("f64", 11, 52);
+    pub const QUADRUPLE: FloatFormat = ieee("f128", 15, 112);
+
+    /// Google's bfloat16: the exponent of f32 with a 7-bit fraction.
+    pub const BFLOAT16: FloatFormat = ieee("bf16", 8, 7);
+
+    /// NVIDIA's TensorFloat-32: the exponent of f32 with the fraction of f16, 19 bits in total.
+    pub const TENSOR_FLOAT32: FloatFormat = ieee("tf32", 8, 10);
+
+    /// OCP FP8 E4M3, which trades infinities for one more binade and saturates at ±448.
+    pub const E4M3: FloatFormat =
+        FloatFormat { name: "e4m3", exponent_bits: 4, fraction_bits: 3, finite_only: true, saturating: true };
+
+    /// OCP FP8 E5M2, which keeps IEEE infinities and NaNs but saturates at ±57344 when converting.
+    pub const E5M2: FloatFormat =
+        FloatFormat { name: "e5m2", exponent_bits: 5, fraction_bits: 2, finite_only: false, saturating: true };
+
+    const FORMATS: [FloatFormat; 8] = [HALF, SINGLE, DOUBLE, QUADRUPLE, BFLOAT16, TENSOR_FLOAT32, E4M3, E5M2];
+
+    /// What a bit pattern stands for.
+    pub enum FloatValue {
+        Finite(BigRational),
+        Infinity,
+        /// A NaN with its quiet bit and the rest of the fraction.
+        NaN { quiet: bool, payload: BigUint },
+    }
+
+    /// Finds the format by its name: `f16`, `f32`, `f64`, `f128`, `bf16`, `tf32`, `e4m3` or `e5m2`.
+    pub fn from_suffix(suffix: &str) -> Option<FloatFormat> {
+        FORMATS.into_iter().find(|format| format.name == suffix)
+    }
+
+    impl FloatFormat {
+        /// Total number of bits.
+        pub fn width(&self) -> u32 {
+            1 + self.exponent_bits + self.fraction_bits
+        }
+
+        fn bias(&self) -> i64 {
+            (1 << (self.exponent_bits - 1)) - 1
+        }
+
+        fn max_exponent_field(&self) -> BigUint {
+            (BigUint::one() << self.exponent_bits) - 1u32
+        }
+
+        /// The bit pattern of the greatest finite value. The next pattern is an infinity,
+        /// or the NaN in formats without infinities.
+        fn max_finite(&self) -> BigUint {
+            if self.finite_only {
+                (BigUint::one() << (self.width() - 1)) - 2u32
+            } else {
+                (self.max_exponent_field() << self.fraction_bits) - 1u32
+            }
+        }
+
+        /// Reads a bit pattern written in hexadecimal, or in binary after `0b`.
+        /// The pattern must fit in the format.
+        pub fn parse_bits(&self, bits: &str) -> Result<BigUint, ErrorKind> {
+            fixed_width::parse_pattern(bits, self.width())
+        }
+
+        /// Rounds the value to the nearest float, ties to even, and returns its bit pattern.
+        /// Values too great for the format become infinities, the NaN in formats without them,
+        /// or the greatest finite value in saturating formats.
+        ///
+        /// **Example:**
+        /// ```
+        /// SINGLE.encode(&pi) // 0x40490FDB
+        /// ```
+        pub fn encode(&self, value: &BigRational) -> BigUint {
+            let sign = if value.is_negative() { BigUint::one() << (self.width() - 1) } else { BigUint::zero() };
+            if value.is_zero() { return sign }
+
+            let (significand, exponent) = self.round(&value.abs());
+            let overflow = if self.saturating { self.max_finite() } else { self.max_finite() + 1u32 };
+
+            // A significand rounded up to the next power of two carries into the exponent field. //
+            sign | ((exponent << self.fraction_bits) + significand).min(overflow)
+        }
+
+        /// Decodes a bit pattern into its sign and value.
+        pub fn decode(&self, bits: &BigUint) -> (bool, FloatValue) {
+            let negative = bits.bit(self.width() as u64 - 1);
+            let exponent = (bits >> self.fraction_bits) & self.max_exponent_field();
+            let fraction = bits & ((BigUint::one() << self.fraction_bits) - 1u32);
+
+            let magnitude = bits & ((BigUint::one() << (self.width() - 1)) - 1u32);
+            if self.finite_only && magnitude > self.max_finite() {
+                return (negative, FloatValue::NaN { quiet: true, payload: BigUint::zero() });
+            }
+            if !self.finite_only && exponent == self.max_exponent_field() {
+                if fraction.is_zero() { return (negative, FloatValue::Infinity) }
+
+                let quiet = fraction.bit(self.fraction_bits as u64 - 1);
+                let payload = fraction & ((BigUint::one() << (self.fraction_bits - 1)) - 1u32);
+                return (negative, FloatValue::NaN { quiet, payload });
+            }
+
+            // Subnormal numbers have no hidden bit and the exponent of the smallest normal number. //
+            let (significand, exponent) = if exponent.is_zero() {
+                (fraction, 1 - self.bias())
+            } else {
+                (fraction + (BigUint::one() << self.fraction_bits), exponent.to_i64().unwrap() - self.bias())
+            };
+
+            let value = BigRational::from(BigInt::from(significand))
+                * BigRational::from(BigInt::from(2)).pow((exponent - self.fraction_bits as i64) as i32);
+            (negative, FloatValue::Finite(if negative { -value } else { value }))
+        }
+
+        /// Shows the fields of a bit pattern and the value it stands for. If the pattern encodes
+        /// the `original` value, also shows how far rounding took it.
+        pub fn describe(&self, bits: &BigUint, original: Option<&BigRational>) -> String {
+            let binary = format!("{:0width$b}", bits, width = self.width() as usize);
+            let (sign, rest) = binary.split_at(1);
+            let (exponent, fraction) = rest.split_at(self.exponent_bits as usize);
+
+            let hex = |field: &str| {
+                let value = BigUint::parse_bytes(field.as_bytes(), 2).unwrap();
+                format!("{} = 0x{:0width$X}", field, value, width = field.len().div_ceil(4))
+            };
+
+            let exponent_field = BigUint::parse_bytes(exponent.as_bytes(), 2).unwrap();
+            let exponent_meaning = if exponent_field.is_zero() && !fraction.contains('1') {
+                "zero".to_owned()
+            } else if exponent_field.is_zero() {
+                format!("subnormal, 2^{}", 1 - self.bias())
+            } else if !self.finite_only && exponent_field == self.max_exponent_field() {
+                "infinity or NaN".to_owned()
+            } else {
+                format!("2^{}", exponent_field.to_i64().unwrap() - self.bias())
+            };
+
+            let (negative, value) = self.decode(bits);
+            let sign_symbol = if negative { "-" } else { "+" };
+            let rounding_error = match (original, &value) {
+                (None, _) => String::new(),
+                (Some(original), FloatValue::Finite(value)) => format!("\nRounding error: {}", converting::write_decimal(value - original)),
+                (Some(_), _) => "\nRounding error: overflow".to_owned(),
+            };
+            let value = match value {
+                FloatValue::Finite(value) if value.is_zero() => format!("{}0", if negative { "-" } else { "" }),
+                FloatValue::Finite(value) => write_exact(&value),
+                FloatValue::Infinity => format!("{}Infinity", sign_symbol),
+                FloatValue::NaN { .. } if self.finite_only => format!("{}NaN", sign_symbol),
+                FloatValue::NaN { quiet, payload } => {
+                    format!("{}NaN, {}, payload 0x{:X}", sign_symbol, if quiet { "quiet" } else { "signaling" }, payload)
+                }
+            };
+
+            format!(
+                "Hex: 0x{:0hex_width$X}\n\
+                Binary: {} {} {}\n\
+                Sign: {} ({})\n\
+                Exponent: {} ({})\n\
+                Fraction: {}\n\
+                Value: {}{}",
+                bits, sign, exponent, fraction, hex(sign), sign_symbol, hex(exponent), exponent_meaning, hex(fraction), value, rounding_error,
+                hex_width = self.width().div_ceil(4) as usize,
+            )
+        }
+
+        /// Rounds a positive value to a significand and an exponent field, so that the bit pattern
+        /// is their sum with the field shifted over the fraction. A normal significand keeps
+        /// its hidden bit, which is why the field is one less than the biased exponent.
+        fn round(&self, magnitude: &BigRational) -> (BigUint, BigUint) {
+            let two = BigRational::from(BigInt::from(2));
+
+            // The bit lengths give a close guess of the binary exponent, which is then corrected. //
+            let mut exponent = magnitude.numer().bits() as i64 - magnitude.denom().bits() as i64;
+            while two.clone().pow(exponent as i32) > *magnitude { exponent -= 1 }
+            while two.clone().pow(exponent as i32 + 1) <= *magnitude { exponent += 1 }
+
+            let min_exponent = 1 - self.bias();
+            let scale = self.fraction_bits as i64 - exponent.max(min_exponent);
+            let significand = converting::round_half_even(&(magnitude * two.pow(scale as i32))).to_biguint().unwrap();
+
+            let field = if exponent < min_exponent { 0 } else { exponent + self.bias() - 1 };
+            (significand, BigUint::from(field as u64))
+        }
+    }
+
+    /// Writes a decoded value in decimal in full. Its denominator is a power of two,
+    /// so its expansion ends after as many digits as the power: n / 2^k = n × 5^k / 10^k.
+    fn write_exact(value: &BigRational) -> String {
+        let places = value.denom().trailing_zeros().unwrap_or(0);
+        let digits = (value.numer().abs() * BigInt::from(5).pow(places)).to_string();
+        let digits = format!("{:0>width$}", digits, width = places as usize + 1);
+        let (whole_part, fractional_part) = digits.split_at(digits.len() - places as usize);
+
+        let mut result = whole_part.to_owned();
+        if !fractional_part.is_empty() {
+            result = format!("{}.{}", result, fractional_part);
+        }
+        if value.is_negative() { result.insert(0, '-') }
+        result
+    }
+}
+
+/// Named sets of digits for positional number systems.
+pub mod alphabets {
+    use std::borrow::Cow;
+    use std::collections::HashSet;
+
+    /// Characters that have a meaning in a query and can't be used as digits.
+    const RESERVED: &str = "-.()_{}[]>";
+
+    /// Digits of a number system, where the position of a digit is its value.
+    #[derive(Clone, PartialEq)]
+    pub struct Alphabet {
+        pub digits: Cow<'static, str>,
+        /// Alphabets with letters of one case only accept both `a` and `A` as the same digit.
+        pub case_sensitive: bool,
+    }
+
+    /// Digits and uppercase latin letters, used for bases up to 36.
+    pub const STANDARD: Alphabet = Alphabet {
+        digits: Cow::Borrowed("0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ"),
+        case_sensitive: false,
+    };
+
+    /// Digits, uppercase and lowercase latin letters, used for bases from 37 to 62.
+    pub const BASE62: Alphabet = Alphabet {
+        digits: Cow::Borrowed("0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz"),
+        case_sensitive: true,
+    };
+
+    /// Bitcoin's base58, which leaves out `0`, `O`, `I` and `l` so that they can't be confused.
+    pub const BITCOIN58: Alphabet = Alphabet {
+        digits: Cow::Borrowed("123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz"),
+        case_sensitive: true,
+    };
+
+    /// The base64 alphabet from RFC 4648.
+    pub const BASE64: Alphabet = Alphabet {
+        digits: Cow::Borrowed("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/"),
+        case_sensitive: true,
+    };
+
+    impl Alphabet {
+        /// Makes an alphabet from the digits a user wrote. Digits must be unique, there must be
+        /// at least two of them, and none of them may be whitespace or a reserved character.
+        pub fn custom(digits: &str) -> Option<Self> {
+            let mut seen = HashSet::new();
+            let valid = digits.chars().all(|digit| {
+                !digit.is_whitespace() && !RESERVED.contains(digit) && seen.insert(digit)
+            });
+
+            if valid && seen.len() >= 2 {
+                Some(Self { digits: Cow::Owned(digits.to_owned()), case_sensitive: true })
+            } else {
+                None
+            }
+        }
+
+        /// Returns the value of the digit, if the alphabet has it.
+        pub fn value_of(&self, digit: char) -> Option<usize> {
+            if self.case_sensitive {
+                self.digits.chars().position(|symb| symb == digit)
+            } else {
+                self.digits.chars().position(|symb| symb == digit.to_ascii_uppercase())
+            }
+        }
+
+        /// Returns the digit for the value. The value must be less than the length of the alphabet.
+        pub fn digit(&self, value: usize) -> char {
+            self.digits.chars().nth(value).unwrap()
+        }
+
+        /// Returns the largest base the alphabet has enough digits for.
+        pub fn max_base(&self) -> i32 {
+            self.digits.chars().count() as i32
+        }
+    }
+
+    /// Reads a number system suffix such as `16`, `62` or `58btc` and returns the base
+    /// with the alphabet its numbers are written in. Digits in braces make a custom alphabet,
+    /// whose base is the number of digits.
+    ///
+    /// **Example:**
+    /// ```
+    /// from_suffix("58btc") // Some((58, BITCOIN58))
+    /// from_suffix("{ACGT}") // Some((4, ACGT))
+    /// ```
+    pub fn from_suffix(suffix: &str) -> Option<(i32, Alphabet)> {
+        if let Some(digits) = suffix.strip_prefix('{').and_then(|rest| rest.strip_suffix('}')) {
+            let alphabet = Alphabet::custom(digits)?;
+            return Some((alphabet.max_base(), alphabet));
+        }
+
+        let (base, alphabet) = match suffix {
+            "58btc" => (58, BITCOIN58),
+            "64" => (64, BASE64),
+            _ => {
+                let base = suffix.parse::<i32>().ok()?;
+                if base <= STANDARD.max_base() { (base, STANDARD) } else { (base, BASE62) }
+            }
+        };
+
+        if (2..=alphabet.max_base()).contains(&base) {
+            Some((base, alphabet))
+        } else {
+            None
+        }
+    }
+}
+
+/// Balanced number systems with an odd base, where digits go from -(base - 1)/2 to (base - 1)/2.
+/// Negative numbers need no sign, since their leading digit is negative.
+pub mod balanced {
+    use std::io::ErrorKind;
+    use num_bigint::BigInt;
+    use num_integer::Integer;
+    use num_rational::BigRational;
+    use num_traits::{Pow, ToPrimitive, Zero};
+    use super::alphabets::STANDARD;
+
+    /// Combining overline that marks a negative digit, e.g. `2̅` is -2.
+    pub const OVERLINE: char = '\u{305}';
+
+    /// Balanced ternary writes -1 as `T`.
+    const TERNARY_MINUS_ONE: char = 'T';
+
+    /// Checks that the base is odd and its digits can be written with the standard alphabet.
+    pub fn is_valid_base(base: i32) -> bool {
+        base % 2 == 1 && (3..=2 * STANDARD.max_base() - 1).contains(&base)
+    }
+
+    /// Reads a balanced number into an exact fraction.
+    /// Balanced ternary also accepts `T` and `-` for -1.
+    ///
+    /// **Example:**
+    /// ```
+    /// parse("1T0", 3) // 6
+    /// parse("1.2̅", 5) // 3/5
+    /// ```
+    pub fn parse(num: &str, base: i32) -> Result<BigRational, ErrorKind> {
+        let max_digit = (base - 1) / 2;
+        let mut digits: Vec<i32> = Vec::new();
+        let mut fractional_length: Option<u32> = None;
+
+        for symb in num.chars() {
+            match symb {
+                '.' if fractional_length.is_none() => fractional_length = Some(0),
+                OVERLINE => match digits.last_mut() {
+                    Some(digit) if *digit > 0 => *digit = -*digit,
+                    _ => return Err(ErrorKind::InvalidData),
+                },
+                'T' | 't' | '-' if base == 3 => digits.push(-1),
+                _ => match STANDARD.value_of(symb) {
+                    Some(value) if value as i32 <= max_digit => digits.push(value as i32),
+                    _ => return Err(ErrorKind::InvalidData),
+                },
+            }
+            if symb != '.' && symb != OVERLINE {
+                fractional_length = fractional_length.map(|length| length + 1);
+            }
+        }
+        if digits.is_empty() { return Err(ErrorKind::InvalidData) }
+
+        let mut result = BigInt::zero();
+        for digit in digits {
+            result = result * base + digit;
+        }
+
+        Ok(BigRational::new(result, BigInt::from(base).pow(fractional_length.unwrap_or(0))))
+    }
+
+    /// Writes the number in the balanced system, rounding it to the nearest number
+    /// with `mantissa_length` digits after the point (10 by default). Trailing zeros are dropped.
+    pub fn format(num: &BigRational, base: i32, mantissa_length: Option<i32>) -> String {
+        let length = mantissa_length.unwrap_or(10).max(0) as usize;
+        let scaled = num * BigRational::from_integer(BigInt::from(base).pow(length as u32));
+        let mut rest = (scaled + BigRational::new(1.into(), 2.into())).floor().to_integer();
+
+        // Least significant digit first
+        let mut digits: Vec<i32> = Vec::new();
+        while !rest.is_zero() || digits.len() <= length {
+            let mut digit = rest.mod_floor(&BigInt::from(base)).to_i32().unwrap();
+            if digit > (base - 1) / 2 { digit -= base }
+            digits.push(digit);
+            rest = (rest - digit) / base;
+        }
+
+        let mut fractional_part: String = digits[..length].iter().rev().map(|&digit| write_digit(digit, base)).collect();
+        while fractional_part.ends_with('0') { fractional_part.pop(); }
+
+        let whole_part: String = digits[length..].iter().rev().map(|&digit| write_digit(digit, base)).collect();
+        let mut result = match whole_part.trim_start_matches('0') {
+            "" => "0".to_owned(),
+            whole_part => whole_part.to_owned(),
+        };
+
+        if !fractional_part.is_empty() {
+            result = format!("{}.{}", result, fractional_part);
+        }
+
+        result
+    }
+
+    fn write_digit(digit: i32, base: i32) -> String {
+        if digit >= 0 {
+            STANDARD.digit(digit as usize).to_string()
+        } else if base == 3 {
+            TERNARY_MINUS_ONE.to_string()
+        } else {
+            format!("{}{}", STANDARD.digit(digit.unsigned_abs() as usize), OVERLINE)
+        }
+    }
+}
+
+/// Number systems with a negative base from -2 to -36, like negabinary. Every integer
+/// has a representation without a sign, since odd positions have a negative weight.
+pub mod negabase {
+    use std::io::ErrorKind;
+    use num_bigint::BigInt;
+    use num_integer::Integer;
+    use num_rational::BigRational;
+    use num_traits::{Pow, ToPrimitive, Zero};
+    use super::alphabets::STANDARD;
+    use super::converting::write_digits;
+
+    pub fn is_valid_base(base: i32) -> bool {
+        (-STANDARD.max_base()..=-2).contains(&base)
+    }
+
+    /// Reads a number written in a negative base into an exact fraction.
+    ///
+    /// **Example:**
+    /// ```
+    /// parse("1101", -2) // -3
+    /// parse("1.1", -2) // 1/2
+    /// ```
+    pub fn parse(num: &str, base: i32) -> Result<BigRational, ErrorKind> {
+        let mut result = BigInt::zero();
+        let mut fractional_length: Option<u32> = None;
+        let mut has_digits = false;
+
+        for symb in num.chars() {
+            if symb == '.' && fractional_length.is_none() {
+                fractional_length = Some(0);
+                continue;
+            }
+
+            match STANDARD.value_of(symb) {
+                Some(value) if (value as i32) < -base => {
+                    result = result * base + value;
+                    fractional_length = fractional_length.map(|length| length + 1);
+                    has_digits = true;
+                }
+                _ => return Err(ErrorKind::InvalidData),
+            }
+        }
+        if !has_digits { return Err(ErrorKind::InvalidData) }
+
+        Ok(BigRational::new(result, BigInt::from(base).pow(fractional_length.unwrap_or(0))))
+    }
+
+    /// Writes the number in a negative base, rounding it to the nearest number
+    /// with `mantissa_length` digits after the point (10 by default). Trailing zeros are dropped.
+    pub fn format(num: &BigRational, base: i32, mantissa_length: Option<i32>) -> String {
+        let length = mantissa_length.unwrap_or(10).max(0) as usize;
+
+        write_digits(&digits(num, base, length), length, &STANDARD)
+    }
+
+    /// Returns the digits of the number rounded to `length` places after the point,
+    /// least significant first. There is always at least one digit before the point.
+    ///
+    /// Digits come from dividing by the base, where the remainder is made non-negative
+    /// by taking it modulo |base| and carrying the difference into the quotient.
+    pub fn digits(num: &BigRational, base: i32, length: usize) -> Vec<usize> {
+        let scaled = num * BigRational::from_integer(BigInt::from(base).pow(length as u32));
+        let mut rest = (scaled + BigRational::new(1.into(), 2.into())).floor().to_integer();
+
+        let mut digits: Vec<usize> = Vec::new();
+        while !rest.is_zero() || digits.len() <= length {
+            let digit = rest.mod_floor(&BigInt::from(-base));
+            rest = (rest - &digit) / base;
+            digits.push(digit.to_usize().unwrap());
+        }
+
+        digits
+    }
+}
+
+/// Positional number systems with a complex base: Knuth's quater-imaginary base 2i with digits
+/// from 0 to 3, and base -1+i with digits 0 and 1. Both write every Gaussian integer without a sign.
+pub mod complex {
+    use std::io::ErrorKind;
+    use num_bigint::BigInt;
+    use num_complex::Complex;
+    use num_integer::Integer;
+    use num_rational::BigRational;
+    use num_traits::{One, ToPrimitive, Zero};
+    use super::alphabets::{Alphabet, STANDARD};
+    use super::converting;
+    use super::negabase;
+
+    pub type ComplexRational = Complex<BigRational>;
+
+    #[derive(Clone, Copy, PartialEq)]
+    pub enum ComplexBase {
+        /// Base 2i. Even positions hold the real part in base -4, odd ones hold half of the imaginary part.
+        QuaterImaginary,
+        /// Base -1+i, whose digits of a Gaussian integer come from dividing by the base with remainder 0 or 1.
+        MinusOnePlusI,
+    }
+
+    impl ComplexBase {
+        /// Reads `2i` or `-1+i`.
+        pub fn from_suffix(suffix: &str) -> Option<Self> {
+            match suffix {
+                "2i" => Some(Self::QuaterImaginary),
+                "-1+i" => Some(Self::MinusOnePlusI),
+                _ => None,
+            }
+        }
+
+        fn value(&self) -> ComplexRational {
+            match self {
+                Self::QuaterImaginary => Complex::new(BigRational::zero(), BigRational::from_integer(2.into())),
+                Self::MinusOnePlusI => Complex::new(-BigRational::one(), BigRational::one()),
+            }
+        }
+
+        fn digit_count(&self) -> usize {
+            match self {
+                Self::QuaterImaginary => 4,
+                Self::MinusOnePlusI => 2,
+            }
+        }
+    }
+
+    /// Reads a number written in a complex base into an exact Gaussian rational.
+    ///
+    /// **Example:**
+    /// ```
+    /// parse("10.2", ComplexBase::QuaterImaginary) // i
+    /// ```
+    pub fn parse(num: &str, base: ComplexBase) -> Result<ComplexRational, ErrorKind> {
+        let radix = base.value();
+        let mut result = ComplexRational::zero();
+        let mut fractional_length: Option<u32> = None;
+        let mut has_digits = false;
+
+        for symb in num.chars() {
+            if symb == '.' && fractional_length.is_none() {
+                fractional_length = Some(0);
+                continue;
+            }
+
+            match STANDARD.value_of(symb) {
+                Some(value) if value < base.digit_count() => {
+                    result = result * radix.clone() + ComplexRational::from(BigRational::from_integer(value.into()));
+                    fractional_length = fractional_length.map(|length| length + 1);
+                    has_digits = true;
+                }
+                _ => return Err(ErrorKind::InvalidData),
+            }
+        }
+        if !has_digits { return Err(ErrorKind::InvalidData) }
+
+        Ok(result / radix.powu(fractional_length.unwrap_or(0)))
+    }
+
+    /// Writes the number in a complex base, rounding it to `mantissa_length` digits
+    /// after the point (10 by default). Trailing zeros are dropped.
+    pub fn format(num: &ComplexRational, base: ComplexBase, mantissa_length: Option<i32>) -> String {
+        let length = mantissa_length.unwrap_or(10).max(0) as usize;
+        let digits = match base {
+            ComplexBase::QuaterImaginary => quater_imaginary_digits(num, length),
+            ComplexBase::MinusOnePlusI => minus_one_plus_i_digits(num, length),
+        };
+
+        converting::write_digits(&digits, length, &STANDARD)
+    }
+
+    /// Reads a complex number such as `3+4i`, `-2.5i` or `7` with both parts written in `num_base`.
+    pub fn parse_literal(num: &str, num_base: i32, alphabet: &Alphabet) -> Result<ComplexRational, ErrorKind> {
+        let num = if let Some(imaginary) = num.strip_suffix('i') {
+            imaginary
+        } else {
+            return Ok(ComplexRational::from(converting::parse_number(num, num_base, alphabet)?));
+        };
+
+        // The imaginary part starts at the last sign that isn't the first character
+        let split = num.char_indices().skip(1)
+            .filter(|&(_, symb)| symb == '+' || symb == '-')
+            .map(|(index, _)| index)
+            .last()
+            .unwrap_or(0);
+        let (real, imaginary) = num.split_at(split);
+
+        let real = if real.is_empty() {
+            BigRational::zero()
+        } else {
+            converting::parse_number(real, num_base, alphabet)?
+        };
+        let imaginary = match imaginary.strip_prefix('+').unwrap_or(imaginary) {
+            "" => BigRational::one(),
+            "-" => -BigRational::one(),
+            imaginary => converting::parse_number(imaginary, num_base, alphabet)?,
+        };
+
+        Ok(Complex::new(real, imaginary))
+    }
+
+    fn quater_imaginary_digits(num: &ComplexRational, length: usize) -> Vec<usize> {
+        // (2i)²ᵏ = (-4)ᵏ and (2i)²ᵏ⁺¹ = 2i·(-4)ᵏ
+        let real = negabase::digits(&num.re, -4, length / 2);
+        let imaginary = negabase::digits(&(&num.im / BigInt::from(2)), -4, length.div_ceil(2));
+
+        let mut digits = vec![0; length + 2 * real.len().max(imaginary.len())];
+        for (index, digit) in real.iter().enumerate() {
+            digits[2 * index + length % 2] = *digit;
+        }
+        for (index, digit) in imaginary.iter().enumerate() {
+            digits[2 * index + 1 - length % 2] = *digit;
+        }
+
+        digits
+    }
+
+    fn minus_one_plus_i_digits(num: &ComplexRational, length: usize) -> Vec<usize> {
+        let scaled = num * ComplexBase::MinusOnePlusI.value().powu(length as u32);
+        let half = BigRational::new(1.into(), 2.into());
+        let mut real = (&scaled.re + &half).floor().to_integer();
+        let mut imaginary = (&scaled.im + &half).floor().to_integer();
+
+        let mut digits: Vec<usize> = Vec::new();
+        while !real.is_zero() || !imaginary.is_zero() || digits.len() <= length {
+            // a + bi is divisible by -1+i exactly when a + b is even
+            let digit = (&real + &imaginary).mod_floor(&BigInt::from(2));
+            real -= &digit;
+            // (a + bi) / (-1 + i) = ((b - a) - (a + b)i) / 2
+            let next_real = (&imaginary - &real) / 2;
+            imaginary = -(&real + &imaginary) / 2;
+            real = next_real;
+            digits.push(digit.to_usize().unwrap());
+        }
+
+        digits
+    }
+}
+
+/// The factorial number system, where the digit at position k (counting from 0 on the right)
+/// has the weight k! and goes from 0 to k. A number of n digits is also the Lehmer code
+/// of a permutation of n elements, so factoradic numbers rank permutations.
+pub mod factoradic {
+    use std::io::ErrorKind;
+    use num_bigint::BigInt;
+    use num_integer::Integer;
+    use num_traits::{One, Signed, ToPrimitive, Zero};
+    use super::alphabets::STANDARD;
+
+    /// Separates digits once some of them don't fit in a single character.
+    const SEPARATOR: char = ':';
+
+    /// The most elements a permutation may have, so that `n`! stays shorter than the longest number.
+    pub const MAX_ELEMENTS: usize = 1000;
+
+    /// Reads a factoradic number. Digits are either single characters, as in `341010`,
+    /// or decimal numbers separated by colons, as in `1:37:0:...:1:0`.
+    pub fn parse(num: &str) -> Result<BigInt, ErrorKind> {
+        let (negative, num) = match num.strip_prefix('-') {
+            Some(magnitude) => (true, magnitude),
+            None => (false, num),
+        };
+
+        let digits: Vec<usize> = if num.contains(SEPARATOR) {
+            num.split(SEPARATOR)
+                .map(|digit| digit.parse::<usize>().map_err(|_| ErrorKind::InvalidData))
+                .collect::<Result<_, _>>()?
+        } else {
+            num.chars()
+                .map(|digit| STANDARD.value_of(digit).ok_or(ErrorKind::InvalidData))
+                .collect::<Result<_, _>>()?
+        };
+        if digits.is_empty() { return Err(ErrorKind::InvalidData) }
+
+        let mut result = BigInt::zero();
+        let mut weight = BigInt::one();
+        for (position, digit) in digits.iter().rev().enumerate() {
+            if *digit > position { return Err(ErrorKind::InvalidData) }
+            if position > 0 { weight *= position }
+            result += &weight * *digit;
+        }
+
+        Ok(if negative { -result } else { result })
+    }
+
+    /// Writes a whole number in the factorial number system, always ending with the 0! digit.
+    ///
+    /// **Example:**
+    /// ```
+    /// format(&463.into()) // "341010"
+    /// ```
+    pub fn format(num: &BigInt) -> String {
+        let digits = digits(num.abs(), 0);
+        let mut result = if digits.iter().all(|&digit| digit < STANDARD.max_base() as usize) {
+            digits.iter().rev().map(|&digit| STANDARD.digit(digit)).collect::<String>()
+        } else {
+            digits.iter().rev().map(|digit| digit.to_string()).collect::<Vec<_>>().join(&SEPARATOR.to_string())
+        };
+
+        if num.is_negative() { result.insert(0, '-') }
+        result
+    }
+
+    /// Returns the Lehmer code of the permutation of `elements` elements with the given rank,
+    /// most significant digit first. Without `elements` the code is as short as the rank allows.
+    /// Returns `None` if the rank is negative or not less than `elements`!,
+    /// or if there are more than `MAX_ELEMENTS` elements.
+    pub fn lehmer_code(rank: &BigInt, elements: Option<usize>) -> Option<Vec<usize>> {
+        if rank.is_negative() || elements.is_some_and(|elements| elements > MAX_ELEMENTS) { return None }
+
+        let mut code = digits(rank.clone(), elements.unwrap_or(0));
+        if let Some(elements) = elements {
+            if code.len() > elements { return None }
+        }
+
+        code.reverse();
+        Some(code)
+    }
+
+    /// Applies a Lehmer code to the elements 1..=n, where each digit picks one of the elements left.
+    pub fn permutation(code: &[usize]) -> Vec<usize> {
+        let mut elements: Vec<usize> = (1..=code.len()).collect();
+        code.iter().map(|&digit| elements.remove(digit)).collect()
+    }
+
+    /// Returns the rank of a permutation of 1..=n among all permutations in lexicographic order.
+    /// Returns `None` if the elements aren't a permutation of 1..=n.
+    pub fn rank(permutation: &[usize]) -> Option<BigInt> {
+        let mut elements: Vec<usize> = (1..=permutation.len()).collect();
+        let mut code = String::new();
+
+        for element in permutation {
+            let digit = elements.iter().position(|candidate| candidate == element)?;
+            elements.remove(digit);
+            code.push_str(&digit.to_string());
+            code.push(SEPARATOR);
+        }
+        code.pop();
+
+        parse(&code).ok()
+    }
+
+    /// Returns factoradic digits least significant first, at least `min_length` of them.
+    fn digits(mut num: BigInt, min_length: usize) -> Vec<usize> {
+        let mut digits: Vec<usize> = vec![0];
+        let mut radix = 2;
+
+        while !num.is_zero() || digits.len() < min_length {
+            let (quotient, digit) = num.div_rem(&BigInt::from(radix));
+            digits.push(digit.to_usize().unwrap());
+            num = quotient;
+            radix += 1;
+        }
+
+        digits
+    }
+}
+
+/// Zeckendorf representation, which writes a whole number as a sum of non-consecutive
+/// Fibonacci numbers 1, 2, 3, 5, 8, ..., and Fibonacci coding, its reversed self-delimiting form.
+pub mod zeckendorf {
+    use std::io::ErrorKind;
+    use num_bigint::BigInt;
+    use num_traits::{One, Signed, Zero};
+    use super::alphabets::STANDARD;
+    use super::converting;
+
+    /// Reads a Zeckendorf representation, most significant digit first.
+    /// Only 0s and 1s are allowed, and no two 1s can be next to each other.
+    pub fn parse(num: &str) -> Result<BigInt, ErrorKind> {
+        if num.is_empty() || num.contains("11") { return Err(ErrorKind::InvalidData) }
+
+        let mut result = BigInt::zero();
+        let (mut weight, mut next) = (BigInt::one(), BigInt::from(2));
+
+        for digit in num.chars().rev() {
+            match STANDARD.value_of(digit) {
+                Some(0) => {}
+                Some(1) => result += &weight,
+                _ => return Err(ErrorKind::InvalidData),
+            }
+            (weight, next) = (next.clone(), weight + next);
+        }
+
+        Ok(result)
+    }
+
+    /// Writes a whole non-negative number in the Zeckendorf representation.
+    /// Returns `None` for negative numbers.
+    ///
+    /// **Example:**
+    /// ```
+    /// format(&100.into()) // Some("1000010100"), 89 + 8 + 3
+    /// ```
+    pub fn format(num: &BigInt) -> Option<String> {
+        if num.is_negative() { return None }
+
+        let mut fibonacci = vec![BigInt::one(), BigInt::from(2)];
+        while fibonacci[fibonacci.len() - 1] <= *num {
+            let next = &fibonacci[fibonacci.len() - 1] + &fibonacci[fibonacci.len() - 2];
+            fibonacci.push(next);
+        }
+
+        // Taking the greatest Fibonacci number each time never takes two neighbours. //
+        let mut remainder = num.clone();
+        let mut digits = vec![0; fibonacci.len()];
+        for (position, weight) in fibonacci.iter().enumerate().rev() {
+            if *weight <= remainder {
+                remainder -= weight;
+                digits[position] = 1;
+            }
+        }
+
+        Some(converting::write_digits(&digits, 0, &STANDARD))
+    }
+
+    /// Reads a Fibonacci code: a Zeckendorf representation written least significant digit first
+    /// and followed by an extra 1, so that every code ends with `11`.
+    pub fn parse_code(code: &str) -> Result<BigInt, ErrorKind> {
+        let digits = code.strip_suffix('1').ok_or(ErrorKind::InvalidData)?;
+        if !digits.ends_with('1') { return Err(ErrorKind::InvalidData) }
+
+        parse(&digits.chars().rev().collect::<String>())
+    }
+
+    /// Writes a positive whole number as a Fibonacci code. Returns `None` for zero and negative numbers.
+    pub fn format_code(num: &BigInt) -> Option<String> {
+        if !num.is_positive() { return None }
+
+        let mut code: String = format(num)?.chars().rev().collect();
+        code.push('1');
+        Some(code)
+    }
+}
+
+/// Roman numerals. Values from 4000 up to 3999999 are written with a vinculum,
+/// an overline that multiplies a letter by 1000: `V̅` is 5000. Input may also use
+/// the apostrophus, where `CIↃ` is 1000 and `IↃↃ` is 5000.
+pub mod roman {
+    use std::io::ErrorKind;
+    use num_bigint::BigInt;
+    use num_traits::{Signed, ToPrimitive};
+    use super::balanced::OVERLINE;
+
+    /// Letters with their values, from the greatest, including the subtractive pairs.
+    const NUMERALS: [(&str, u64); 13] = [
+        ("M", 1000), ("CM", 900), ("D", 500), ("CD", 400), ("C", 100), ("XC", 90),
+        ("L", 50), ("XL", 40), ("X", 10), ("IX", 9), ("V", 5), ("IV", 4), ("I", 1),
+    ];
+
+    /// The reversed C of the apostrophus.
+    const APOSTROPHUS: char = 'Ↄ';
+
+    /// Zero, from the Latin *nulla*.
+    const ZERO: &str = "N";
+
+    /// The greatest number a single vinculum can write.
+    const MAX_VALUE: u64 = 3_999_999;
+
+    /// Checks that the text is made of Roman numerals, so that it can be sent without a number system.
+    pub fn is_numeral(num: &str) -> bool {
+        num == ZERO || symbols(num).is_some()
+    }
+
+    /// Reads a Roman numeral. Strict parsing only accepts the usual way of writing a number,
+    /// while lenient parsing also accepts forms like `IIII` or `IM`: a symbol is subtracted
+    /// if a greater one follows it and added otherwise.
+    pub fn parse(num: &str, strict: bool) -> Result<BigInt, ErrorKind> {
+        if num == ZERO { return Ok(BigInt::from(0)) }
+
+        let symbols = symbols(num).ok_or(ErrorKind::InvalidData)?;
+        let mut result: i64 = 0;
+        for (position, &value) in symbols.iter().enumerate() {
+            match symbols.get(position + 1) {
+                Some(&next) if next > value => result -= value as i64,
+                _ => result += value as i64,
+            }
+        }
+
+        if strict && format(&BigInt::from(result)).and_then(|canonical| symbols_of(&canonical)) != Some(symbols) {
+            return Err(ErrorKind::InvalidData);
+        }
+
+        Ok(BigInt::from(result))
+    }
+
+    /// Writes a number from 0 to 3999999 in Roman numerals.
+    /// Returns `None` for negative and greater numbers.
+    ///
+    /// **Example:**
+    /// ```
+    /// format(&1994.into()) // Some("MCMXCIV")
+    /// format(&5010.into()) // Some("V̅X")
+    /// ```
+    pub fn format(num: &BigInt) -> Option<String> {
+        if num.is_negative() { return None }
+
+        let num = num.to_u64().filter(|&num| num <= MAX_VALUE)?;
+        if num == 0 { return Some(ZERO.to_owned()) }
+        if num < 4000 { return Some(write(num)) }
+
+        let thousands: String = write(num / 1000).chars().flat_map(|letter| [letter, OVERLINE]).collect();
+        Some(thousands + &write(num % 1000))
+    }
+
+    /// Writes a number from 1 to 3999 with plain letters.
+    fn write(mut num: u64) -> String {
+        let mut result = String::new();
+        for (letters, value) in NUMERALS {
+            while num >= value {
+                result.push_str(letters);
+                num -= value;
+            }
+        }
+        result
+    }
+
+    /// Splits a numeral into the values of its symbols. Letters may be lowercase.
+    fn symbols(num: &str) -> Option<Vec<u64>> {
+        symbols_of(&num.to_uppercase().replace('ↄ', &APOSTROPHUS.to_string()))
+    }
+
+    fn symbols_of(num: &str) -> Option<Vec<u64>> {
+        let chars: Vec<char> = num.chars().collect();
+        let count = |from: usize, symbol: char| chars[from..].iter().take_while(|&&char| char == symbol).count();
+
+        let mut symbols = Vec::new();
+        let mut position = 0;
+        while position < chars.len() {
+            // An apostrophus numeral: C..CIↃ..Ↄ is a power of ten, IↃ..Ↄ is five times a power of ten. //
+            let c_count = count(position, 'C');
+            let i_position = position + c_count;
+            if chars.get(i_position) == Some(&'I') && chars.get(i_position + 1) == Some(&APOSTROPHUS) {
+                let apostrophus_count = count(i_position + 1, APOSTROPHUS);
+                if apostrophus_count > 3 { return None }
+
+                if c_count == 0 {
+                    symbols.push(5 * 10u64.pow(apostrophus_count as u32 + 1));
+                } else if apostrophus_count <= c_count {
+                    // Extra C's in front are plain hundreds. //
+                    symbols.extend(vec![100; c_count - apostrophus_count]);
+                    symbols.push(10u64.pow(apostrophus_count as u32 + 2));
+                } else {
+                    return None;
+                }
+                position = i_position + 1 + apostrophus_count;
+                continue;
+            }
+
+            let value = NUMERALS.iter()
+                .find(|(letters, _)| letters.len() == 1 && letters.starts_with(chars[position]))?.1;
+            if chars.get(position + 1) == Some(&OVERLINE) {
+                symbols.push(value * 1000);
+                position += 2;
+            } else {
+                symbols.push(value);
+                position += 1;
+            }
+        }
+
+        (!symbols.is_empty()).then_some(symbols)
+    }
+}
+
+/// Bijective numeration, where digits go from 1 to the base and there is no zero digit.
+/// Spreadsheet columns are bijective base 26 written with letters: A is 1, Z is 26, AA is 27.
+pub mod bijective {
+    use std::io::ErrorKind;
+    use num_bigint::BigInt;
+    use num_integer::Integer;
+    use num_traits::{Signed, ToPrimitive, Zero};
+    use super::alphabets::STANDARD;
+
+    /// The base of spreadsheet column names.
+    pub const COLUMN_BASE: i32 = 26;
+
+    /// Standard digits A to Z stand for 10 to 35, so a letter has to be shifted to count from 1.
+    const LETTER_OFFSET: usize = 9;
+
+    /// Checks that every digit from 1 to the base can be written with the standard alphabet.
+    pub fn is_valid_base(base: i32) -> bool {
+        (2..STANDARD.max_base()).contains(&base)
+    }
+
+    /// Reads a bijective number. Digits are 1-9A-Z, or only letters starting from A if `letters` is set.
+    ///
+    /// **Example:**
+    /// ```
+    /// parse("XFD", 26, true) // Ok(16384)
+    /// parse("1A", 10, false) // Ok(20)
+    /// ```
+    pub fn parse(num: &str, base: i32, letters: bool) -> Result<BigInt, ErrorKind> {
+        if num.is_empty() { return Err(ErrorKind::InvalidData) }
+
+        let mut result = BigInt::zero();
+        for symb in num.chars() {
+            let value = match STANDARD.value_of(symb) {
+                Some(value) if letters && value > LETTER_OFFSET => value - LETTER_OFFSET,
+                Some(value) if !letters && value > 0 => value,
+                _ => return Err(ErrorKind::InvalidData),
+            };
+            if value > base as usize { return Err(ErrorKind::InvalidData) }
+
+            result = result * base + value;
+        }
+
+        Ok(result)
+    }
+
+    /// Writes a positive whole number in bijective numeration. Returns `None` for zero and negative numbers,
+    /// which have no digits.
+    pub fn format(num: &BigInt, base: i32, letters: bool) -> Option<String> {
+        if !num.is_positive() { return None }
+
+        let mut num = num.clone();
+        let mut result = Vec::new();
+        while !num.is_zero() {
+            // Taking one off first makes the remainder go from 1 to the base instead of from 0. //
+            let (quotient, remainder) = (num - 1u32).div_rem(&BigInt::from(base));
+            let value = remainder.to_usize().unwrap() + 1;
+            result.push(STANDARD.digit(if letters { value + LETTER_OFFSET } else { value }));
+            num = quotient;
+        }
+
+        Some(result.iter().rev().collect())
+    }
+}
+
+/// Fixed-width integers, the way a machine stores them. Signed integers can be stored
+/// in two's complement, one's complement or sign-magnitude form.
+pub mod fixed_width {
+    use std::io::ErrorKind;
+    use num_bigint::{BigInt, BigUint, Sign};
+    use num_integer::Integer;
+    use num_traits::{One, Signed};
+    use super::alphabets::STANDARD;
+    use super::converting;
+
+    /// The widest integer a user can ask for.
+    const MAX_WIDTH: u32 = 1024;
+
+    /// Reads a bit pattern written in hexadecimal, or in binary after `0b`.
+    /// The pattern must fit in `width` bits.
+    pub fn parse_pattern(pattern: &str, width: u32) -> Result<BigUint, ErrorKind> {
+        let (digits, base) = match pattern.strip_prefix("0b") {
+            Some(digits) => (digits, 2),
+            None => (pattern.strip_prefix("0x").unwrap_or(pattern), 16),
+        };
+        if digits.starts_with('-') { return Err(ErrorKind::InvalidData) }
+
+        let pattern = converting::parse_whole(digits, base, &STANDARD)?.to_biguint().ok_or(ErrorKind::InvalidData)?;
+        if pattern.bits() > width as u64 { return Err(ErrorKind::InvalidData) }
+
+        Ok(pattern)
+    }
+
+    /// The width and signedness of an integer, written as `i8`, `u16` and so on.
+    #[derive(Clone, Copy, PartialEq)]
+    pub struct IntegerWidth {
+        pub bits: u32,
+        pub signed: bool,
+    }
+
+    impl IntegerWidth {
+        /// Returns `None` if the integer would have no bits or too many of them.
+        pub fn new(bits: u32, signed: bool) -> Option<Self> {
+            (1..=MAX_WIDTH).contains(&bits).then_some(Self { bits, signed })
+        }
+
+        pub fn from_suffix(suffix: &str) -> Option<Self> {
+            let (signed, bits) = match suffix.split_at_checked(1)? {
+                ("i", bits) => (true, bits),
+                ("u", bits) => (false, bits),
+                _ => return None,
+            };
+
+            Self::new(bits.parse::<u32>().ok()?, signed)
+        }
+
+        /// The least and the greatest value the integer holds in two's complement.
+        pub fn range(&self) -> (BigInt, BigInt) {
+            if self.signed {
+                (-(BigInt::one() << (self.bits - 1)), (BigInt::one() << (self.bits - 1)) - 1)
+            } else {
+                (BigInt::from(0), (BigInt::one() << self.bits) - 1)
+            }
+        }
+
+        /// Checks that a bit pattern is no wider than the integer.
+        pub fn holds(&self, pattern: &BigUint) -> bool {
+            pattern.bits() <= self.bits as u64
+        }
+
+        /// Stores the value in two's complement, or as a plain binary number if the integer is unsigned.
+        /// Returns `None` if the value doesn't fit.
+        ///
+        /// **Example:**
+        /// ```
+        /// IntegerWidth { bits: 8, signed: true }.twos_complement(&(-5).into()) // Some(0b11111011)
+        /// ```
+        pub fn twos_complement(&self, value: &BigInt) -> Option<BigUint> {
+            let (min, max) = self.range();
+            if *value < min || *value > max { return None }
+
+            let pattern = if value.is_negative() { value + (BigInt::one() << self.bits) } else { value.clone() };
+            pattern.to_biguint()
+        }
+
+        /// Stores a signed value in one's complement, where negative values have every bit inverted.
+        /// Returns `None` if the value doesn't fit.
+        pub fn ones_complement(&self, value: &BigInt) -> Option<BigUint> {
+            // Both forms have a negative zero, so the magnitude has to fit in the bits after the sign. //
+            self.twos_complement(&value.abs())?;
+
+            let pattern = if value.is_negative() { value + (BigInt::one() << self.bits) - 1 } else { value.clone() };
+            pattern.to_biguint()
+        }
+
+        /// Stores a signed value as a sign bit followed by its magnitude.
+        /// Returns `None` if the value doesn't fit.
+        pub fn sign_magnitude(&self, value: &BigInt) -> Option<BigUint> {
+            self.twos_complement(&value.abs())?;
+
+            let sign = if value.is_negative() { BigUint::one() << (self.bits - 1) } else { BigUint::from(0u32) };
+            Some(sign | value.magnitude())
+        }
+
+        /// Reads a bit pattern stored in two's complement, or as a plain binary number if the integer is unsigned.
+        pub fn read_twos_complement(&self, pattern: &BigUint) -> BigInt {
+            let value = BigInt::from_biguint(Sign::Plus, pattern.clone());
+            if self.is_negative(pattern) { value - (BigInt::one() << self.bits) } else { value }
+        }
+
+        pub fn read_ones_complement(&self, pattern: &BigUint) -> BigInt {
+            let value = BigInt::from_biguint(Sign::Plus, pattern.clone());
+            if self.is_negative(pattern) { value - (BigInt::one() << self.bits) + 1 } else { value }
+        }
+
+        pub fn read_sign_magnitude(&self, pattern: &BigUint) -> BigInt {
+            let magnitude = pattern & ((BigUint::one() << (self.bits - 1)) - 1u32);
+            let sign = if self.is_negative(pattern) { Sign::Minus } else { Sign::Plus };
+            BigInt::from_biguint(sign, magnitude)
+        }
+
+        /// Keeps the lowest bits of the value, the way machine arithmetic wraps around.
+        ///
+        /// **Example:**
+        /// ```
+        /// IntegerWidth { bits: 8, signed: true }.wrap(&200.into()) // -56
+        /// ```
+        pub fn wrap(&self, value: &BigInt) -> BigInt {
+            let pattern = value.mod_floor(&(BigInt::one() << self.bits));
+            self.read_twos_complement(pattern.magnitude())
+        }
+
+        /// Rotates the bits of a pattern to the left, the highest bits coming back at the bottom.
+        pub fn rotate_left(&self, pattern: &BigUint, amount: u64) -> BigUint {
+            let amount = amount % self.bits as u64;
+            let mask = (BigUint::one() << self.bits) - 1u32;
+            ((pattern << amount) | (pattern >> (self.bits as u64 - amount))) & mask
+        }
+
+        /// Reverses the order of the bits of a pattern.
+        pub fn reverse(&self, pattern: &BigUint) -> BigUint {
+            let mut reversed = BigUint::from(0u32);
+            for bit in 0..self.bits as u64 {
+                reversed.set_bit(self.bits as u64 - 1 - bit, pattern.bit(bit));
+            }
+            reversed
+        }
+
+        fn is_negative(&self, pattern: &BigUint) -> bool {
+            self.signed && pattern.bit(self.bits as u64 - 1)
+        }
+    }
+}
+
+/// Fixed-point numbers in Q format. `Q4.12` has 4 integer bits, the sign bit among them,
+/// and 12 fraction bits, so it is a signed 16-bit integer counting 1/4096ths. `UQ4.12` is unsigned.
+pub mod fixed_point {
+    use std::io::ErrorKind;
+    use num_bigint::{BigInt, BigUint};
+    use num_rational::BigRational;
+    use super::converting;
+    use super::fixed_width;
+    use super::fixed_width::IntegerWidth;
+
+    #[derive(Clone, Copy, PartialEq)]
+    pub struct QFormat {
+        integer_bits: u32,
+        fraction_bits: u32,
+        signed: bool,
+    }
+
+    impl QFormat {
+        /// Reads a format like `Q4.12` or `UQ8.8`. A signed format needs an integer bit for the sign.
+        pub fn from_suffix(suffix: &str) -> Option<Self> {
+            let (signed, bits) = match suffix.get(..2)? {
+                "UQ" | "uq" => (false, &suffix[2..]),
+                _ => (true, suffix.strip_prefix(['Q', 'q'])?),
+            };
+            let (integer_bits, fraction_bits) = bits.split_once('.')?;
+            let format = Self { integer_bits: integer_bits.parse().ok()?, fraction_bits: fraction_bits.parse().ok()?, signed };
+
+            if signed && format.integer_bits == 0 { return None }
+            IntegerWidth::new(format.integer_bits.checked_add(format.fraction_bits)?, signed)?;
+            Some(format)
+        }
+
+        /// The integer that stores the number.
+        pub fn width(&self) -> IntegerWidth {
+            IntegerWidth::new(self.integer_bits + self.fraction_bits, self.signed).unwrap()
+        }
+
+        /// Rounds the value to the nearest step, ties to even, and saturates at the ends of the range.
+        /// Returns the raw integer and whether it saturated.
+        ///
+        /// **Example:**
+        /// ```
+        /// Q4_12.encode(&pi) // (12868, false), 0x3244
+        /// ```
+        pub fn encode(&self, value: &BigRational) -> (BigInt, bool) {
+            let raw = converting::round_half_even(&(value * self.scale()));
+            let (min, max) = self.width().range();
+
+            if raw > max {
+                (max, true)
+            } else if raw < min {
+                (min, true)
+            } else {
+                (raw, false)
+            }
+        }
+
+        /// Reads the raw integer from a bit pattern written in hexadecimal, or in binary after `0b`.
+        pub fn parse_raw(&self, pattern: &str) -> Result<BigInt, ErrorKind> {
+            let pattern = fixed_width::parse_pattern(pattern, self.width().bits)?;
+            Ok(self.width().read_twos_complement(&pattern))
+        }
+
+        /// The number a raw integer stands for.
+        pub fn value(&self, raw: &BigInt) -> BigRational {
+            BigRational::from(raw.clone()) / self.scale()
+        }
+
+        /// Shows the raw integer, its bits and the number it stands for. If the integer encodes
+        /// the `original` value, also shows the quantization error.
+        pub fn describe(&self, raw: &BigInt, original: Option<&BigRational>, saturated: bool) -> String {
+            let pattern: BigUint = self.width().twos_complement(raw).unwrap();
+            let value = self.value(raw);
+
+            let mut result = format!(
+                "Raw: {}\nHex: 0x{:0hex_width$X}\nValue: {}",
+                raw, pattern, converting::write_decimal(value.clone()),
+                hex_width = self.width().bits.div_ceil(4) as usize,
+            );
+            if let Some(original) = original {
+                result.push_str(&format!("\nQuantization error: {}", converting::write_decimal(value - original)));
+            }
+            if saturated {
+                result.push_str("\nSaturated: the number is out of range");
+            }
+
+            result
+        }
+
+        fn scale(&self) -> BigRational {
+            BigRational::from(BigInt::from(1) << self.fraction_bits)
+        }
+    }
+}
+
+/// Binary-coded decimal, where every decimal digit gets its own four bits.
+pub mod bcd {
+    use std::io::ErrorKind;
+    use num_bigint::BigInt;
+    use num_traits::{Signed, Zero};
+    use super::alphabets::STANDARD;
+
+    /// A way to code decimal digits in binary.
+    #[derive(Clone, Copy, PartialEq)]
+    pub enum DecimalCode {
+        /// 8421 BCD with two digits in a byte: 1994 is `0x1994`.
+        Packed,
+        /// 8421 BCD with one digit in the low nibble of a byte: 1994 is `0x01090904`.
+        Unpacked,
+        /// Every digit plus three, so that the nines' complement is the bitwise complement.
+        Excess3,
+        /// The 2421 code, where 0-4 are written as in BCD and 5-9 as the complements of 4-0.
+        Aiken,
+    }
+
+    impl DecimalCode {
+        /// Finds the code by its name: `bcd`, `ubcd`, `xs3` or `aiken`.
+        pub fn from_suffix(suffix: &str) -> Option<Self> {
+            match suffix {
+                "bcd" => Some(Self::Packed),
+                "ubcd" => Some(Self::Unpacked),
+                "xs3" => Some(Self::Excess3),
+                "aiken" => Some(Self::Aiken),
+                _ => None,
+            }
+        }
+
+        /// Codes a whole non-negative number, returning a nibble per digit.
+        /// Returns `None` for negative numbers.
+        pub fn encode(&self, num: &BigInt) -> Option<Vec<u8>> {
+            if num.is_negative() { return None }
+
+            let nibbles = num.to_string().bytes().map(|digit| self.encode_digit(digit - b'0')).collect();
+            Some(nibbles)
+        }
+
+        /// Reads a number from a nibble per digit. Fails on a nibble that codes no digit.
+        pub fn decode(&self, nibbles: &[u8]) -> Result<BigInt, ErrorKind> {
+            let mut result = BigInt::zero();
+            for &nibble in nibbles {
+                result = result * 10u32 + self.decode_digit(nibble).ok_or(ErrorKind::InvalidData)?;
+            }
+            Ok(result)
+        }
+
+        /// Finds the first nibble that codes no digit, with the position of its digit
+        /// counted from 1 on the left.
+        pub fn find_invalid(&self, nibbles: &[u8]) -> Option<(usize, u8)> {
+            nibbles.iter().position(|&nibble| self.decode_digit(nibble).is_none())
+                .map(|index| (index + 1, nibbles[index]))
+        }
+
+        /// Reads the digit nibbles of a pattern written in hexadecimal, or in binary after `0b`.
+        /// In unpacked BCD the high nibble of every byte must be zero.
+        ///
+        /// **Example:**
+        /// ```
+        /// DecimalCode::Unpacked.parse_nibbles("0x0109") // Ok(vec![1, 9])
+        /// ```
+        pub fn parse_nibbles(&self, pattern: &str) -> Result<Vec<u8>, ErrorKind> {
+            let mut nibbles: Vec<u8> = if let Some(bits) = pattern.strip_prefix("0b") {
+                let bits: Vec<u8> = bits.bytes().map(|bit| bit.wrapping_sub(b'0')).collect();
+                if bits.is_empty() || bits.iter().any(|&bit| bit > 1) { return Err(ErrorKind::InvalidData) }
+
+                let padding = vec![0; (4 - bits.len() % 4) % 4];
+                let bits = [padding, bits].concat();
+                bits.chunks(4).map(|nibble| nibble.iter().fold(0, |value, bit| value * 2 + bit)).collect()
+            } else {
+                pattern.strip_prefix("0x").unwrap_or(pattern).chars()
+                    .map(|digit| STANDARD.value_of(digit).filter(|&value| value < 16).map(|value| value as u8))
+                    .collect::<Option<_>>()
+                    .ok_or(ErrorKind::InvalidData)?
+            };
+            if nibbles.is_empty() { return Err(ErrorKind::InvalidData) }
+
+            if *self == Self::Unpacked {
+                if nibbles.len() % 2 == 1 { nibbles.insert(0, 0) }
+                if nibbles.iter().step_by(2).any(|&zone| zone != 0) { return Err(ErrorKind::InvalidData) }
+                nibbles = nibbles.into_iter().skip(1).step_by(2).collect();
+            }
+
+            Ok(nibbles)
+        }
+
+        /// Shows the bits of every digit, the pattern in hexadecimal, and the number it stands for.
+        pub fn describe(&self, nibbles: &[u8]) -> String {
+            let digit_width = if *self == Self::Unpacked { 8 } else { 4 };
+            let binary: Vec<String> = nibbles.iter().map(|nibble| format!("{:0width$b}", nibble, width = digit_width)).collect();
+            let hex: String = nibbles.iter().map(|&nibble| format!("{:0width$X}", nibble, width = digit_width / 4)).collect();
+            let value = self.decode(nibbles).unwrap();
+
+            format!("Binary: {}\nHex: 0x{}\nValue: {}", binary.join(" "), hex, value)
+        }
+
+        fn encode_digit(&self, digit: u8) -> u8 {
+            match self {
+                Self::Packed | Self::Unpacked => digit,
+                Self::Excess3 => digit + 3,
+                Self::Aiken if digit < 5 => digit,
+                Self::Aiken => digit + 6,
+            }
+        }
+
+        fn decode_digit(&self, nibble: u8) -> Option<u8> {
+            let digit = match self {
+                Self::Packed | Self::Unpacked => nibble,
+                Self::Excess3 => nibble.checked_sub(3)?,
+                Self::Aiken if nibble < 5 => nibble,
+                Self::Aiken => nibble.checked_sub(6).filter(|&digit| digit >= 5)?,
+            };
+            (digit < 10).then_some(digit)
+        }
+    }
+}
+
+/// Reflected Gray codes, where neighbouring numbers differ in a single digit, and by one in it.
+/// A digit is reflected, counting down instead of up, while the number formed by the digits
+/// before it is odd. In binary this is the usual `n ^ (n >> 1)`.
+pub mod gray {
+    use std::io::ErrorKind;
+    use num_bigint::BigInt;
+    use num_integer::Integer;
+    use num_traits::{Signed, ToPrimitive, Zero};
+    use super::alphabets::STANDARD;
+    use super::converting;
+
+    /// Checks that the digits of the base can be written with the standard alphabet.
+    pub fn is_valid_base(base: i32) -> bool {
+        (2..=STANDARD.max_base()).contains(&base)
+    }
+
+    /// Writes a whole non-negative number in the reflected Gray code of the base.
+    /// Returns `None` for negative numbers.
+    ///
+    /// **Example:**
+    /// ```
+    /// encode(&13.into(), 2) // Some("1011")
+    /// ```
+    pub fn encode(num: &BigInt, base: i32) -> Option<String> {
+        if num.is_negative() { return None }
+
+        let mut num = num.clone();
+        let mut digits = Vec::new();
+        loop {
+            let (higher, digit) = num.div_rem(&BigInt::from(base));
+            let digit = digit.to_usize().unwrap();
+            digits.push(if higher.is_odd() { base as usize - 1 - digit } else { digit });
+
+            if higher.is_zero() { break }
+            num = higher;
+        }
+
+        Some(converting::write_digits(&digits, 0, &STANDARD))
+    }
+
+    /// Reads a number written in the reflected Gray code of the base.
+    pub fn decode(num: &str, base: i32) -> Result<BigInt, ErrorKind> {
+        if num.is_empty() { return Err(ErrorKind::InvalidData) }
+
+        let mut result = BigInt::zero();
+        for symb in num.chars() {
+            let digit = match STANDARD.value_of(symb) {
+                Some(digit) if (digit as i32) < base => digit,
+                _ => return Err(ErrorKind::InvalidData),
+            };
+            let digit = if result.is_odd() { base as usize - 1 - digit } else { digit };
+            result = result * base + digit;
+        }
+
+        Ok(result)
     }
-}
\ No newline at end of file
+}
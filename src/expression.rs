@@ -0,0 +1,485 @@
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_rational::BigRational;
+use num_traits::{One, Pow, Signed, ToPrimitive, Zero};
+
+use crate::converter::ValidErrorKind;
+use crate::digits::complex::ComplexRational;
+use crate::digits::fixed_width::IntegerWidth;
+
+/// The most bits a power may take, four times the longest number written in binary.
+pub const MAX_POWER_BITS: u64 = 16384;
+
+/// The most bits any number may take while an expression is evaluated, so that a chain
+/// of operations can't grow without limit. A single power may take all of them.
+pub const MAX_TOTAL_BITS: u64 = MAX_POWER_BITS;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    /// Exact division, `/`.
+    Divide,
+    /// Division rounded toward −∞, `//`.
+    IntegerDivide,
+    /// The remainder of `//`, with the sign of the divisor.
+    Modulo,
+    /// A whole power, `^` or `**`.
+    Power,
+    And,
+    Or,
+    /// Exclusive or, `xor`, since `^` is taken by powers.
+    Xor,
+    ShiftLeft,
+    /// An arithmetic shift, `>>`, which keeps the sign.
+    ShiftRight,
+    /// Rotations, `rol` and `ror`, only work with an integer width.
+    RotateLeft,
+    RotateRight,
+}
+
+impl Operator {
+    fn from_symbol(symbol: &str) -> Option<Self> {
+        match symbol {
+            "+" => Some(Self::Add),
+            "-" => Some(Self::Subtract),
+            "*" => Some(Self::Multiply),
+            "/" => Some(Self::Divide),
+            "//" => Some(Self::IntegerDivide),
+            "%" => Some(Self::Modulo),
+            "^" | "**" => Some(Self::Power),
+            "&" => Some(Self::And),
+            "|" => Some(Self::Or),
+            "xor" => Some(Self::Xor),
+            "<<" => Some(Self::ShiftLeft),
+            ">>" => Some(Self::ShiftRight),
+            "rol" => Some(Self::RotateLeft),
+            "ror" => Some(Self::RotateRight),
+            _ => None,
+        }
+    }
+
+    /// Checks that the operator takes the bits of whole numbers.
+    fn is_bitwise(&self) -> bool {
+        matches!(
+            self,
+            Self::And | Self::Or | Self::Xor | Self::ShiftLeft | Self::ShiftRight | Self::RotateLeft | Self::RotateRight
+        )
+    }
+
+    /// Checks that the right operand of the operator is a count rather than a value: a power or a shift.
+    fn takes_count(&self) -> bool {
+        matches!(self, Self::Power | Self::ShiftLeft | Self::ShiftRight | Self::RotateLeft | Self::RotateRight)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Token<'a> {
+    /// A number with its number system, like `FF_16`.
+    Number(&'a str),
+    Operator(Operator),
+    /// Bitwise not, `~`.
+    Not,
+    /// Bit reversal, `rev`, which only works with an integer width.
+    Reverse,
+    OpenParen,
+    CloseParen,
+}
+
+/// An expression tree. Numbers are kept as they are written until the expression is evaluated.
+#[derive(Debug, PartialEq)]
+pub enum Expression<'a> {
+    Number(&'a str),
+    Negation(Box<Expression<'a>>),
+    Not(Box<Expression<'a>>),
+    Reverse(Box<Expression<'a>>),
+    Binary(Operator, Box<Expression<'a>>, Box<Expression<'a>>),
+}
+
+/// Characters that end a number, unless they are part of it as described in `number_length`.
+const DELIMITERS: &str = "+-*/%^&|<>~()";
+
+/// Words that stand for operators. They can't be followed by a letter, a digit or `_`.
+const WORDS: [&str; 4] = ["xor", "rol", "ror", "rev"];
+
+/// Splits an expression into tokens. Operators and parentheses need no spaces around them:
+/// `(FF_16+1011_2)*7_8`, `~0x0F`. A `-` is always an operator, and it is a unary minus
+/// where no number comes before it.
+pub fn tokenize(text: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = text.trim_start();
+
+    while let Some(symb) = rest.chars().next() {
+        let word = WORDS.iter().find(|word| {
+            rest.strip_prefix(**word).is_some_and(|after| after.chars().next().is_none_or(|next| !next.is_alphanumeric() && next != '_'))
+        });
+        let (token, length) = if let Some(word) = word {
+            let token = if *word == "rev" { Token::Reverse } else { Token::Operator(Operator::from_symbol(word).unwrap()) };
+            (token, word.len())
+        } else if let Some(operator) = rest.get(..2).and_then(Operator::from_symbol) {
+            (Token::Operator(operator), 2)
+        } else if let Some(operator) = Operator::from_symbol(&rest[..symb.len_utf8()]) {
+            (Token::Operator(operator), 1)
+        } else {
+            match symb {
+                '(' => (Token::OpenParen, 1),
+                ')' => (Token::CloseParen, 1),
+                '~' => (Token::Not, 1),
+                // A lone `<` or `>` is no operator, and is left for the number reader to refuse. //
+                _ => {
+                    let length = number_length(rest).max(symb.len_utf8());
+                    (Token::Number(&rest[..length]), length)
+                }
+            }
+        };
+
+        tokens.push(token);
+        rest = rest[length..].trim_start();
+    }
+
+    tokens
+}
+
+/// Finds how many bytes the number at the start of the text takes. Some delimiters belong
+/// to a number: a sign after an exponent mark, as in `1e-3_10` and `1^3_36`, a sign before
+/// the imaginary part of a complex literal, as in `3+4i_10`, a repeating block after the point,
+/// as in `0.(3)_10`, and a negative or complex base, as in `1101_-2` and `11101_-1+i`.
+/// Alphabets and radix lists in braces and brackets are taken whole.
+fn number_length(text: &str) -> usize {
+    let symbols: Vec<(usize, char)> = text.char_indices().collect();
+    let symbol = |index: usize| symbols.get(index).map(|&(_, symb)| symb);
+    let is_digit = |index: usize| symbol(index).is_some_and(|symb| symb.is_ascii_digit());
+    let literal = ["0x", "0X", "0o", "0O", "0b", "0B"].iter().any(|prefix| text.starts_with(prefix));
+
+    // The digits go up to the number system. //
+    let mut index = 0;
+    let mut point = false;
+    while let Some(symb) = symbol(index) {
+        let exponent_sign = !literal && matches!(symb, '+' | '-') && index > 0
+            && matches!(symbols[index - 1].1, 'e' | 'E' | 'p' | 'P' | '^') && is_digit(index + 1);
+        let exponent_mark = !literal && symb == '^' && (is_digit(index + 1) || matches!(symbol(index + 1), Some('+' | '-')));
+        let imaginary_sign = matches!(symb, '+' | '-') && index > 0 && {
+            let mut next = index + 1;
+            while symbol(next).is_some_and(|symb| symb.is_ascii_digit() || symb == '.') { next += 1 }
+            symbol(next) == Some('i') && symbol(next + 1) == Some('_')
+        };
+
+        match symb {
+            '_' => break,
+            '(' if point => {
+                while symbol(index).is_some_and(|symb| symb != ')') { index += 1 }
+                index += 1;
+            }
+            _ if exponent_sign || exponent_mark || imaginary_sign => index += 1,
+            _ if symb.is_whitespace() || DELIMITERS.contains(symb) => break,
+            _ => {
+                point |= symb == '.';
+                index += 1;
+            }
+        }
+    }
+
+    // The number system may be negative or complex, or be written in braces or brackets. //
+    if symbol(index) == Some('_') {
+        index += 1;
+        match symbol(index) {
+            Some(open @ ('{' | '[')) => {
+                let close = if open == '{' { '}' } else { ']' };
+                while symbol(index).is_some_and(|symb| symb != close) { index += 1 }
+                index += 1;
+            }
+            _ => {
+                if symbol(index) == Some('-') { index += 1 }
+                while symbol(index).is_some_and(|symb| symb.is_alphanumeric() || symb == '!') { index += 1 }
+                if matches!(symbol(index), Some('+' | '-')) && symbol(index + 1) == Some('i') { index += 2 }
+            }
+        }
+    }
+
+    symbols.get(index).map_or(text.len(), |&(position, _)| position)
+}
+
+/// Checks that the text is an expression rather than a single number, which may have a sign.
+/// Text that can't be parsed is only taken for an expression if it has an operator in it,
+/// so that a number like `(3).1_10` is still refused as a number.
+pub fn is_expression(text: &str) -> bool {
+    let tokens = tokenize(text);
+    match tokens[..] {
+        [Token::Number(_)] | [Token::Operator(Operator::Subtract), Token::Number(_)] => false,
+        _ => {
+            parse(text).is_ok()
+                || tokens.iter().any(|token| matches!(token, Token::Operator(_) | Token::Not | Token::Reverse))
+        }
+    }
+}
+
+/// Parses an expression. From the tightest to the loosest, operators bind in this order:
+/// powers, unary `-`, `~` and `rev`, then `*`, `/`, `//` and `%`, then `+` and `-`,
+/// then shifts and rotations, then `&`, `xor` and `|`.
+/// Powers group to the right, other operators to the left.
+pub fn parse(text: &str) -> Result<Expression<'_>, ValidErrorKind> {
+    let tokens = tokenize(text);
+    let mut parser = Parser { tokens: &tokens, position: 0 };
+
+    let expression = parser.or()?;
+    if parser.position != tokens.len() {
+        return Err(ValidErrorKind::InvalidExpression);
+    }
+
+    Ok(expression)
+}
+
+struct Parser<'t, 'a> {
+    tokens: &'t [Token<'a>],
+    position: usize,
+}
+
+impl<'a> Parser<'_, 'a> {
+    fn next_operator(&self, operators: &[Operator]) -> Option<Operator> {
+        match self.tokens.get(self.position) {
+            Some(Token::Operator(operator)) if operators.contains(operator) => Some(*operator),
+            _ => None,
+        }
+    }
+
+    /// Parses operands joined by the operators, grouping them to the left.
+    fn chain(
+        &mut self,
+        operators: &[Operator],
+        operand: fn(&mut Self) -> Result<Expression<'a>, ValidErrorKind>,
+    ) -> Result<Expression<'a>, ValidErrorKind> {
+        let mut left = operand(self)?;
+        while let Some(operator) = self.next_operator(operators) {
+            self.position += 1;
+            left = Expression::Binary(operator, Box::new(left), Box::new(operand(self)?));
+        }
+
+        Ok(left)
+    }
+
+    fn or(&mut self) -> Result<Expression<'a>, ValidErrorKind> {
+        self.chain(&[Operator::Or], Self::xor)
+    }
+
+    fn xor(&mut self) -> Result<Expression<'a>, ValidErrorKind> {
+        self.chain(&[Operator::Xor], Self::and)
+    }
+
+    fn and(&mut self) -> Result<Expression<'a>, ValidErrorKind> {
+        self.chain(&[Operator::And], Self::shift)
+    }
+
+    fn shift(&mut self) -> Result<Expression<'a>, ValidErrorKind> {
+        let operators = [Operator::ShiftLeft, Operator::ShiftRight, Operator::RotateLeft, Operator::RotateRight];
+        self.chain(&operators, Self::sum)
+    }
+
+    fn sum(&mut self) -> Result<Expression<'a>, ValidErrorKind> {
+        self.chain(&[Operator::Add, Operator::Subtract], Self::product)
+    }
+
+    fn product(&mut self) -> Result<Expression<'a>, ValidErrorKind> {
+        let operators = [Operator::Multiply, Operator::Divide, Operator::IntegerDivide, Operator::Modulo];
+        self.chain(&operators, Self::unary)
+    }
+
+    fn unary(&mut self) -> Result<Expression<'a>, ValidErrorKind> {
+        let unary: fn(Box<Expression<'a>>) -> Expression<'a> = match self.tokens.get(self.position) {
+            Some(Token::Operator(Operator::Subtract)) => Expression::Negation,
+            Some(Token::Not) => Expression::Not,
+            Some(Token::Reverse) => Expression::Reverse,
+            _ => return self.power(),
+        };
+        self.position += 1;
+
+        Ok(unary(Box::new(self.unary()?)))
+    }
+
+    fn power(&mut self) -> Result<Expression<'a>, ValidErrorKind> {
+        let base = self.primary()?;
+        if self.next_operator(&[Operator::Power]).is_some() {
+            self.position += 1;
+            return Ok(Expression::Binary(Operator::Power, Box::new(base), Box::new(self.unary()?)));
+        }
+
+        Ok(base)
+    }
+
+    fn primary(&mut self) -> Result<Expression<'a>, ValidErrorKind> {
+        let token = self.tokens.get(self.position).ok_or(ValidErrorKind::InvalidExpression)?;
+        self.position += 1;
+
+        match token {
+            Token::Number(number) => Ok(Expression::Number(number)),
+            Token::OpenParen => {
+                let inner = self.or()?;
+                if self.tokens.get(self.position) != Some(&Token::CloseParen) {
+                    return Err(ValidErrorKind::InvalidExpression);
+                }
+                self.position += 1;
+                Ok(inner)
+            }
+            _ => Err(ValidErrorKind::InvalidExpression),
+        }
+    }
+}
+
+impl<'a> Expression<'a> {
+    /// Evaluates the expression exactly, reading every number with `read`.
+    /// With an integer width every number must be a whole number that fits in it,
+    /// and every result wraps around like in machine arithmetic.
+    pub fn evaluate<F>(&self, read: &F, width: Option<IntegerWidth>) -> Result<ComplexRational, ValidErrorKind>
+    where
+        F: Fn(&str) -> Result<ComplexRational, ValidErrorKind>,
+    {
+        let result = match self {
+            Expression::Number(number) => {
+                let number = read(number)?;
+                if let Some(width) = width {
+                    if width.twos_complement(&whole(&number)?).is_none() { return Err(ValidErrorKind::Overflow) }
+                }
+                return Ok(number);
+            }
+            Expression::Negation(operand) => -operand.evaluate(read, width)?,
+            Expression::Not(operand) => {
+                let operand = whole(&operand.evaluate(read, width)?)?;
+                ComplexRational::from(BigRational::from(-operand - 1))
+            }
+            Expression::Reverse(operand) => {
+                let width = width.ok_or(ValidErrorKind::MissingWidth)?;
+                let operand = whole(&operand.evaluate(read, Some(width))?)?;
+                let pattern = width.reverse(&width.twos_complement(&operand).unwrap());
+                ComplexRational::from(BigRational::from(width.read_twos_complement(&pattern)))
+            }
+            Expression::Binary(operator, left, right) => {
+                apply(*operator, left.evaluate(read, width)?, right.evaluate(read, width)?, width)?
+            }
+        };
+
+        match width {
+            Some(width) => Ok(ComplexRational::from(BigRational::from(width.wrap(&whole(&result)?)))),
+            None => Ok(result),
+        }
+    }
+
+    /// Checks that the expression takes the bits of its numbers somewhere.
+    pub fn is_bitwise(&self) -> bool {
+        match self {
+            Expression::Number(_) => false,
+            Expression::Negation(operand) => operand.is_bitwise(),
+            Expression::Not(_) | Expression::Reverse(_) => true,
+            Expression::Binary(operator, left, right) => operator.is_bitwise() || left.is_bitwise() || right.is_bitwise(),
+        }
+    }
+
+    /// The numbers whose bits make the result, leaving out powers and shift counts.
+    /// A negated number, like `-8_10`, is taken as one, telling that it is negated.
+    pub fn bit_operands(&self) -> Vec<(bool, &'a str)> {
+        match self {
+            Expression::Number(number) => vec![(false, number)],
+            Expression::Negation(operand) => match **operand {
+                Expression::Number(number) => vec![(true, number)],
+                _ => operand.bit_operands(),
+            },
+            Expression::Not(operand) | Expression::Reverse(operand) => operand.bit_operands(),
+            Expression::Binary(operator, left, _) if operator.takes_count() => left.bit_operands(),
+            Expression::Binary(_, left, right) => [left.bit_operands(), right.bit_operands()].concat(),
+        }
+    }
+}
+
+fn apply(
+    operator: Operator,
+    left: ComplexRational,
+    right: ComplexRational,
+    width: Option<IntegerWidth>,
+) -> Result<ComplexRational, ValidErrorKind> {
+    let result = match operator {
+        _ if operator.is_bitwise() => {
+            let (left, right) = (whole(&left)?, whole(&right)?);
+            ComplexRational::from(BigRational::from(apply_bitwise(operator, left, right, width)?))
+        }
+        Operator::Add => left + right,
+        Operator::Subtract => left - right,
+        Operator::Multiply => left * right,
+        Operator::Divide if right.is_zero() => return Err(ValidErrorKind::DivisionByZero),
+        Operator::Divide => left / right,
+        Operator::IntegerDivide | Operator::Modulo => {
+            let (left, right) = (real(left)?, real(right)?);
+            if right.is_zero() { return Err(ValidErrorKind::DivisionByZero) }
+
+            let quotient = (&left / &right).floor();
+            let result = if operator == Operator::Modulo { left - right * quotient } else { quotient };
+            ComplexRational::from(result)
+        }
+        _ => power(left, right)?,
+    };
+
+    // Results wrap around in a fixed width, and without one every result counts against the budget. //
+    if width.is_none() && size(&result) > MAX_TOTAL_BITS {
+        return Err(ValidErrorKind::InvalidExpression);
+    }
+    Ok(result)
+}
+
+/// Applies a bitwise operator. Negative numbers without a width behave as if they had
+/// infinitely many ones in front, so `~x` is `-x - 1`.
+fn apply_bitwise(operator: Operator, left: BigInt, right: BigInt, width: Option<IntegerWidth>) -> Result<BigInt, ValidErrorKind> {
+    let count = || match right.to_u64() {
+        Some(count) if count <= MAX_POWER_BITS => Ok(count),
+        Some(_) => Err(ValidErrorKind::NumberTooLong),
+        None => Err(ValidErrorKind::InvalidExpression),
+    };
+
+    match operator {
+        Operator::And => Ok(left & right),
+        Operator::Or => Ok(left | right),
+        Operator::Xor => Ok(left ^ right),
+        Operator::ShiftLeft => Ok(left << count()?),
+        Operator::ShiftRight => Ok(left.div_floor(&(BigInt::one() << count()?))),
+        _ => {
+            let width = width.ok_or(ValidErrorKind::MissingWidth)?;
+            let amount = count()? % width.bits as u64;
+            let amount = if operator == Operator::RotateLeft { amount } else { (width.bits as u64 - amount) % width.bits as u64 };
+            let pattern = width.rotate_left(&width.twos_complement(&left).unwrap(), amount);
+            Ok(width.read_twos_complement(&pattern))
+        }
+    }
+}
+
+/// Raises a number to a whole power, refusing the powers that would take too many bits.
+fn power(base: ComplexRational, exponent: ComplexRational) -> Result<ComplexRational, ValidErrorKind> {
+    let exponent = real(exponent)?;
+    if !exponent.is_integer() { return Err(ValidErrorKind::InvalidExpression) }
+    if base.is_zero() && exponent.is_negative() { return Err(ValidErrorKind::DivisionByZero) }
+
+    let exponent = exponent.to_integer();
+    if BigInt::from(size(&base)) * exponent.abs() > BigInt::from(MAX_POWER_BITS) {
+        return Err(ValidErrorKind::NumberTooLong);
+    }
+
+    let exponent = exponent.to_i32().ok_or(ValidErrorKind::NumberTooLong)?;
+    if base.im.is_zero() {
+        Ok(ComplexRational::from(base.re.pow(exponent)))
+    } else {
+        Ok(base.powi(exponent))
+    }
+}
+
+/// The bits of the numerators and denominators of both parts of a number.
+fn size(number: &ComplexRational) -> u64 {
+    let bits = |part: &BigRational| part.numer().bits() + part.denom().bits();
+    bits(&number.re) + bits(&number.im)
+}
+
+/// Takes the real part of a number for the operators that only work on real numbers.
+fn real(number: ComplexRational) -> Result<BigRational, ValidErrorKind> {
+    if number.im.is_zero() { Ok(number.re) } else { Err(ValidErrorKind::InvalidExpression) }
+}
+
+/// Takes a number as a whole number for the operators that work on bits.
+pub fn whole(number: &ComplexRational) -> Result<BigInt, ValidErrorKind> {
+    if !number.im.is_zero() { return Err(ValidErrorKind::InvalidExpression) }
+    if number.re.is_integer() { Ok(number.re.to_integer()) } else { Err(ValidErrorKind::UnrepresentableNumber) }
+}
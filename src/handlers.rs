@@ -15,6 +15,7 @@ pub async fn handle_command(
     bot: AutoSend<Bot>,
     message: Message,
     command: Command,
+    journal: Arc<Journal>,
 ) -> HandlerResult {
     match command {
         Command::Help => {
@@ -23,6 +24,20 @@ pub async fn handle_command(
         Command::FormatGuide => {
             executors::format_guide(bot, message).await?;
         }
+        Command::Rounding(mode) => {
+            executors::rounding(bot, message, journal, mode.trim()).await?;
+        }
+        Command::Explain(query) => {
+            let query = query.trim();
+            let rounding = journal.rounding_mode(message.from().unwrap().id).unwrap_or_default();
+            let converter = Converter::explain_from_message(query, rounding);
+
+            if let Some(explanation) = converter.result {
+                executors::explanation(bot, message, &explanation).await?;
+            } else if let Some(error_kind) = converter.error_kind {
+                executors::conversion_error(bot, message, error_kind, query).await?;
+            }
+        }
     }
 
     Ok(())
@@ -63,7 +78,8 @@ pub async fn handle_message(
             }
         }
 
-        let converter = Converter::convert_from_message(text);
+        let rounding = journal.rounding_mode(message.from().unwrap().id).unwrap_or_default();
+        let converter = Converter::convert_with_rounding(text, rounding);
 
         if let Some(result) = converter.result {
             executors::result(bot, message, &result).await?;
@@ -7,6 +7,7 @@ use std::error::Error;
 use std::sync::{Arc, Mutex};
 use rusqlite::Error::SqliteFailure;
 use crate::debug;
+use crate::digits::converting::RoundingMode;
 
 /// The database for the bot
 pub struct Journal {
@@ -33,7 +34,7 @@ impl Journal {
                 match error {
                     SqliteFailure(error, message) => {
                         if let Some(msg) = message {
-                            if !(msg == "table users already exists") {
+                            if msg != "table users already exists" {
                                 debug::print_debug_error(error);
                                 debug::print_debug_message("[ ERROR ] Can't create database");
                             }
@@ -46,6 +47,18 @@ impl Journal {
             }
         }
 
+        if let Err(ref error) = connection.execute(
+            "CREATE TABLE IF NOT EXISTS rounding_modes (
+                id  INTEGER,
+                mode  TEXT NOT NULL,
+                PRIMARY KEY ( ID )\
+            )",
+            [],
+        ) {
+            debug::print_debug_error(error);
+            debug::print_debug_message("[ ERROR ] Can't create the rounding modes table");
+        }
+
         Ok(
             Self {
                 connection: Arc::new(Mutex::new(connection)),
@@ -76,9 +89,7 @@ impl Journal {
             let mut prepared = db.prepare("SELECT id FROM users").unwrap();
 
             let users_iter = prepared.query_map([], |row| {
-                Ok(
-                    row.get::<usize, u64>(0)?
-                )
+                row.get::<usize, u64>(0)
             }).unwrap();
 
 
@@ -101,14 +112,33 @@ impl Journal {
         Ok(())
     }
 
+    /// Returns the rounding mode the user has chosen with `/rounding`, if any.
+    pub fn rounding_mode(&self, id: UserId) -> Option<RoundingMode> {
+        let db = self.connection.lock().unwrap();
+        let mode = db.query_row(
+            "SELECT mode FROM rounding_modes WHERE id = ?",
+            [id.0],
+            |row| row.get::<usize, String>(0),
+        ).ok()?;
+
+        RoundingMode::from_name(&mode)
+    }
+
+    pub fn set_rounding_mode(&self, id: UserId, mode: RoundingMode) -> Result<(), Box<dyn Error>> {
+        self.connection.lock().unwrap().execute(
+            "INSERT OR REPLACE INTO rounding_modes VALUES ( ?1, ?2 )",
+            rusqlite::params![id.0, mode.name()],
+        )?;
+
+        Ok(())
+    }
+
     pub fn count_users(&self) -> Result<usize, Box<dyn Error>> {
         let db = self.connection.lock().unwrap();
         let mut prepared = db.prepare("SELECT id FROM users")?;
 
         let users_iter = prepared.query_map([], |row| {
-            Ok(
-                row.get::<usize, u64>(0)?
-            )
+            row.get::<usize, u64>(0)
         })?;
 
         let mut count = 0;
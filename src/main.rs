@@ -15,6 +15,8 @@ mod digits;
 
 mod converter;
 
+mod expression;
+
 mod journal;
 use journal::Journal;
 
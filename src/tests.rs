@@ -1,78 +1,1230 @@
 mod digits_test {
+    use std::cmp::Ordering;
+    use num_bigint::BigUint;
+    use num_complex::Complex;
+    use num_rational::BigRational;
     use crate::digits::converting;
+    use crate::digits::converting::{MixedRadix, RoundingMode, Steps};
+    use crate::digits::alphabets::{Alphabet, BASE62, BASE64, BITCOIN58, STANDARD};
+    use crate::digits::balanced;
+    use crate::digits::bcd::DecimalCode;
+    use crate::digits::bijective;
+    use crate::digits::complex;
+    use crate::digits::complex::ComplexBase;
+    use crate::digits::factoradic;
+    use crate::digits::gray;
+    use crate::digits::fixed_point::QFormat;
+    use crate::digits::fixed_width::IntegerWidth;
+    use crate::digits::ieee754;
+    use crate::digits::ieee754::FloatValue;
+    use crate::digits::negabase;
+    use crate::digits::roman;
+    use crate::digits::zeckendorf;
 
     #[test]
     fn any_whole_to_decimal_1_() {
-        assert_eq!(converting::any_whole_to_decimal("1110001", 2).unwrap(), "113");
-        assert_eq!(converting::any_whole_to_decimal("1375", 8).unwrap(), "765");
-        assert_eq!(converting::any_whole_to_decimal("A8F13", 16).unwrap(), "691987");
-        assert_eq!(converting::any_whole_to_decimal("A8F-3", 16).is_err(), true);
+        assert_eq!(converting::any_whole_to_decimal("1110001", 2, &STANDARD).unwrap(), "113");
+        assert_eq!(converting::any_whole_to_decimal("1375", 8, &STANDARD).unwrap(), "765");
+        assert_eq!(converting::any_whole_to_decimal("A8F13", 16, &STANDARD).unwrap(), "691987");
+        assert!(converting::any_whole_to_decimal("A8F-3", 16, &STANDARD).is_err());
+    }
+
+    #[test]
+    fn any_whole_to_decimal_2_() {
+        assert_eq!(
+            converting::any_whole_to_decimal(&"F".repeat(64), 16, &STANDARD).unwrap(),
+            "115792089237316195423570985008687907853269984665640564039457584007913129639935"
+        );
     }
 
     #[test]
     fn any_fractional_to_decimal_1_() {
-        assert_eq!(converting::any_fractional_to_decimal("112.345", 8).unwrap(), "74.447265625");
-        assert_eq!(converting::any_fractional_to_decimal("10.1221", 3).unwrap(), "3.6419753086419755");
-        assert_eq!(converting::any_fractional_to_decimal("A3.F14", 16).unwrap(), "163.9423828125");
+        let to_decimal = |num, num_base| {
+            converting::any_fractional_to_any_periodic(num, num_base, 10, 100, &STANDARD, &STANDARD, None).unwrap().unwrap().result
+        };
+        assert_eq!(to_decimal("112.345", 8), "74.447265625");
+        assert_eq!(to_decimal("10.1221", 3), "3.(641975308)");
+        assert_eq!(to_decimal("A3.F14", 16), "163.9423828125");
     }
 
     #[test]
     fn decimal_whole_to_any_1_() {
-        assert_eq!(converting::decimal_whole_to_any("1014", 2).unwrap(), "1111110110");
+        assert_eq!(converting::decimal_whole_to_any("1014", 2, &STANDARD, None).unwrap(), "1111110110");
     }
 
     #[test]
     fn decimal_whole_to_any_2_() {
-        assert_eq!(converting::decimal_whole_to_any("31599", 8).unwrap(), "75557");
+        assert_eq!(converting::decimal_whole_to_any("31599", 8, &STANDARD, None).unwrap(), "75557");
     }
 
     #[test]
     fn decimal_whole_to_any_3_() {
-        assert_eq!(converting::decimal_whole_to_any("1680135541", 16).unwrap(), "6424D575");
+        assert_eq!(converting::decimal_whole_to_any("1680135541", 16, &STANDARD, None).unwrap(), "6424D575");
+    }
+
+    #[test]
+    fn decimal_whole_to_any_4_() {
+        let decimal = "1234567890".repeat(120);
+        let septenary = converting::decimal_whole_to_any(&decimal, 7, &STANDARD, None).unwrap();
+        assert_eq!(converting::any_whole_to_decimal(&septenary, 7, &STANDARD).unwrap(), decimal);
+    }
+
+    #[test]
+    fn decimal_whole_to_any_5_() {
+        assert_eq!(converting::decimal_whole_to_any("0", 2, &STANDARD, None).unwrap(), "0");
+        assert_eq!(converting::decimal_whole_to_any("-255", 16, &STANDARD, None).unwrap(), "-FF");
     }
 
     #[test]
     fn decimal_fractional_to_any_1_() {
-        assert_eq!(converting::decimal_fractional_to_any("113.55", 2, None).unwrap(), "1110001.1000110011");
+        assert_eq!(converting::any_fractional_to_any_rounded("113.55", 10, 2, None, RoundingMode::TowardZero, &STANDARD, &STANDARD, None).unwrap().0, "1110001.1000110011");
     }
 
     #[test]
     fn decimal_fractional_to_any_2_() {
-        assert_eq!(converting::decimal_fractional_to_any("31599.4846", 3, None).unwrap(), "1121100100.1110020211");
+        assert_eq!(converting::any_fractional_to_any_rounded("31599.4846", 10, 3, None, RoundingMode::TowardZero, &STANDARD, &STANDARD, None).unwrap().0, "1121100100.1110020211");
     }
 
     #[test]
     fn decimal_fractional_to_any_3_() {
-        assert_eq!(converting::decimal_fractional_to_any("12.5", 2, None).unwrap(), "1100.1");
+        assert_eq!(converting::any_fractional_to_any_rounded("12.5", 10, 2, None, RoundingMode::TowardZero, &STANDARD, &STANDARD, None).unwrap().0, "1100.1");
     }
 
     #[test]
     fn decimal_fractional_to_any_4_() {
-        assert_eq!(converting::decimal_fractional_to_any("1234.5678", 16, None).unwrap(), "4D2.915B573EAB");
+        assert_eq!(converting::any_fractional_to_any_rounded("1234.5678", 10, 16, None, RoundingMode::TowardZero, &STANDARD, &STANDARD, None).unwrap().0, "4D2.915B573EAB");
+    }
+
+    #[test]
+    fn decimal_fractional_to_any_5_() {
+        assert_eq!(
+            converting::any_fractional_to_any_rounded("0.1", 10, 3, Some(50), RoundingMode::TowardZero, &STANDARD, &STANDARD, None).unwrap().0,
+            "0.002200220022002200220022002200220022002200220022"
+        );
+    }
+
+    #[test]
+    fn decimal_fractional_to_any_6_() {
+        assert_eq!(
+            converting::any_fractional_to_any_rounded("0.12345678901234567890123456789", 10, 10, Some(50), RoundingMode::TowardZero, &STANDARD, &STANDARD, None).unwrap().0,
+            "0.12345678901234567890123456789"
+        );
+    }
+
+    #[test]
+    fn decimal_fractional_to_any_7_() {
+        assert_eq!(
+            converting::any_fractional_to_any_rounded("3.14159265358979323846264338327950288", 10, 16, Some(30), RoundingMode::TowardZero, &STANDARD, &STANDARD, None).unwrap().0,
+            "3.243F6A8885A308D313198A2E03706D"
+        );
     }
 
     #[test]
     fn any_whole_to_any_1_() {
-        assert_eq!(converting::any_whole_to_any("0010101011101010", 2, 16).unwrap(), "2AEA");
+        assert_eq!(converting::any_whole_to_any("0010101011101010", 2, 16, &STANDARD, &STANDARD, None).unwrap(), "2AEA");
     }
 
     #[test]
     fn any_whole_to_any_2_() {
-        assert_eq!(converting::any_whole_to_any("1445363", 7, 3).unwrap(), "100222101122");
+        assert_eq!(converting::any_whole_to_any("1445363", 7, 3, &STANDARD, &STANDARD, None).unwrap(), "100222101122");
     }
 
     #[test]
     fn any_whole_to_any_3_() {
-        assert_eq!(converting::any_whole_to_any("A15BB3", 16, 8).unwrap(), "50255663");
+        assert_eq!(converting::any_whole_to_any("A15BB3", 16, 8, &STANDARD, &STANDARD, None).unwrap(), "50255663");
+    }
+
+    #[test]
+    fn any_whole_to_any_4_() {
+        let hex = "F".repeat(1024);
+        let binary = converting::any_whole_to_any(&hex, 16, 2, &STANDARD, &STANDARD, None).unwrap();
+        assert_eq!(binary, "1".repeat(4096));
+        assert_eq!(converting::any_whole_to_any(&binary, 2, 16, &STANDARD, &STANDARD, None).unwrap(), hex);
+    }
+
+    #[test]
+    fn any_whole_to_any_5_() {
+        assert_eq!(converting::any_whole_to_any("-1011", 2, 10, &STANDARD, &STANDARD, None).unwrap(), "-11");
+        assert_eq!(converting::any_whole_to_any("-0", 2, 10, &STANDARD, &STANDARD, None).unwrap(), "0");
+    }
+
+    #[test]
+    fn any_whole_to_any_6_() {
+        assert_eq!(converting::any_whole_to_any("18446744073709551615", 10, 62, &STANDARD, &BASE62, None).unwrap(), "LygHa16AHYF");
+        assert_eq!(converting::any_whole_to_any("18446744073709551615", 10, 58, &STANDARD, &BITCOIN58, None).unwrap(), "jpXCZedGfVQ");
+        assert_eq!(converting::any_whole_to_any("D/", 64, 10, &BASE64, &STANDARD, None).unwrap(), "255");
+        assert_eq!(converting::any_whole_to_any("0", 10, 58, &STANDARD, &BITCOIN58, None).unwrap(), "1");
+    }
+
+    #[test]
+    fn any_whole_to_any_7_() {
+        assert_eq!(converting::any_whole_to_any("a", 62, 10, &BASE62, &STANDARD, None).unwrap(), "36");
+        assert_eq!(converting::any_whole_to_any("A", 62, 10, &BASE62, &STANDARD, None).unwrap(), "10");
+        assert_eq!(converting::any_whole_to_any("ff", 16, 10, &STANDARD, &STANDARD, None).unwrap(), "255");
+        assert!(converting::any_whole_to_any("0l", 58, 10, &BITCOIN58, &STANDARD, None).is_err());
     }
 
     #[test]
     fn any_fractional_to_any_1_() {
-        assert_eq!(converting::any_fractional_to_any("13.5533", 8, 16, None).unwrap(), "B.B5B");
+        assert_eq!(converting::any_fractional_to_any_rounded("13.5533", 8, 16, None, RoundingMode::TowardZero, &STANDARD, &STANDARD, None).unwrap().0, "B.B5B");
     }
 
     #[test]
     fn any_fractional_to_any_3_() {
-        assert_eq!(converting::any_fractional_to_any("0.55357", 8, 16, None).unwrap(), "0.B5DE");
+        assert_eq!(converting::any_fractional_to_any_rounded("0.55357", 8, 16, None, RoundingMode::TowardZero, &STANDARD, &STANDARD, None).unwrap().0, "0.B5DE");
+    }
+
+    #[test]
+    fn any_fractional_to_any_4_() {
+        assert_eq!(converting::any_fractional_to_any_rounded("-0.75", 10, 2, None, RoundingMode::TowardZero, &STANDARD, &STANDARD, None).unwrap().0, "-0.11");
+        assert_eq!(converting::any_fractional_to_any_rounded("-0.0", 10, 2, None, RoundingMode::TowardZero, &STANDARD, &STANDARD, None).unwrap().0, "0");
+        assert_eq!(converting::any_fractional_to_any_rounded("12.0", 10, 2, None, RoundingMode::TowardZero, &STANDARD, &STANDARD, None).unwrap().0, "1100");
+    }
+
+    #[test]
+    fn any_fractional_to_any_5_() {
+        assert_eq!(converting::any_fractional_to_any_rounded("0.(3)", 10, 3, None, RoundingMode::TowardZero, &STANDARD, &STANDARD, None).unwrap().0, "0.1");
+        assert_eq!(converting::any_fractional_to_any_rounded("0.1(6)", 10, 6, None, RoundingMode::TowardZero, &STANDARD, &STANDARD, None).unwrap().0, "0.1");
+        assert_eq!(converting::any_fractional_to_any_rounded("0.(9)", 10, 10, None, RoundingMode::TowardZero, &STANDARD, &STANDARD, None).unwrap().0, "1");
+    }
+
+    #[test]
+    fn any_fractional_to_any_periodic_1_() {
+        let expansion = converting::any_fractional_to_any_periodic("0.1", 10, 2, 50, &STANDARD, &STANDARD, None).unwrap().unwrap();
+        assert_eq!(expansion.result, "0.0(0011)");
+        assert_eq!((expansion.pre_period_length, expansion.period_length), (1, 4));
+
+        let expansion = converting::any_fractional_to_any_periodic("-0.1", 3, 10, 50, &STANDARD, &STANDARD, None).unwrap().unwrap();
+        assert_eq!(expansion.result, "-0.(3)");
+        assert_eq!((expansion.pre_period_length, expansion.period_length), (0, 1));
+    }
+
+    #[test]
+    fn any_fractional_to_any_periodic_2_() {
+        let expansion = converting::any_fractional_to_any_periodic("12.5", 10, 2, 50, &STANDARD, &STANDARD, None).unwrap().unwrap();
+        assert_eq!(expansion.result, "1100.1");
+        assert_eq!(expansion.period_length, 0);
+
+        // 1/97 repeats every 96 digits
+        assert!(converting::any_fractional_to_any_periodic("0.(010309278350515463917525773195876288659793814432989690721649484536082474226804123711340206185567)", 10, 10, 50, &STANDARD, &STANDARD, None).unwrap().is_none());
+    }
+
+    #[test]
+    fn any_whole_to_any_8_() {
+        let dna = Alphabet::custom("ACGT").unwrap();
+        let dozenal = Alphabet::custom("0123456789↊↋").unwrap();
+        assert_eq!(converting::any_whole_to_any("GATTACA", 4, 10, &dna, &STANDARD, None).unwrap(), "9156");
+        assert_eq!(converting::any_whole_to_any("9156", 10, 4, &STANDARD, &dna, None).unwrap(), "GATTACA");
+        assert_eq!(converting::any_whole_to_any("↋↊", 12, 10, &dozenal, &STANDARD, None).unwrap(), "142");
+        assert!(converting::any_whole_to_any("gattaca", 4, 10, &dna, &STANDARD, None).is_err());
+    }
+
+    #[test]
+    fn any_fractional_to_any_periodic_3_() {
+        let dozenal = Alphabet::custom("0123456789↊↋").unwrap();
+        let expansion = converting::any_fractional_to_any_periodic("0.↊", 12, 10, 50, &dozenal, &STANDARD, None).unwrap().unwrap();
+        assert_eq!(expansion.result, "0.8(3)");
+        let expansion = converting::any_fractional_to_any_periodic("0.8(3)", 10, 12, 50, &STANDARD, &dozenal, None).unwrap().unwrap();
+        assert_eq!(expansion.result, "0.↊");
+    }
+
+    #[test]
+    fn alphabet_custom_1_() {
+        assert!(Alphabet::custom("ACGT").is_some());
+        assert!(Alphabet::custom("ACGA").is_none());
+        assert!(Alphabet::custom("A").is_none());
+        assert!(Alphabet::custom("A-B").is_none());
+        assert!(Alphabet::custom("A B").is_none());
+    }
+
+    #[test]
+    fn balanced_parse_1_() {
+        assert_eq!(balanced::parse("1T0", 3).unwrap(), BigRational::from_integer(6.into()));
+        assert_eq!(balanced::parse("-01", 3).unwrap(), BigRational::from_integer((-8).into()));
+        assert_eq!(balanced::parse("22\u{305}2", 5).unwrap(), BigRational::from_integer(42.into()));
+        assert_eq!(balanced::parse("0.1", 3).unwrap(), BigRational::new(1.into(), 3.into()));
+        assert!(balanced::parse("12", 3).is_err());
+        assert!(balanced::parse("0\u{305}", 5).is_err());
+    }
+
+    #[test]
+    fn balanced_format_1_() {
+        assert_eq!(balanced::format(&BigRational::from_integer(6.into()), 3, None), "1T0");
+        assert_eq!(balanced::format(&BigRational::from_integer((-8).into()), 3, None), "T01");
+        assert_eq!(balanced::format(&BigRational::from_integer(0.into()), 3, None), "0");
+        assert_eq!(balanced::format(&BigRational::from_integer(42.into()), 5, None), "22\u{305}2");
+        assert_eq!(balanced::format(&BigRational::new(1.into(), 2.into()), 3, Some(3)), "1.TTT");
+        assert_eq!(balanced::format(&BigRational::new((-1).into(), 3.into()), 3, None), "0.T");
+    }
+
+    #[test]
+    fn negabase_parse_1_() {
+        assert_eq!(negabase::parse("1101", -2).unwrap(), BigRational::from_integer((-3).into()));
+        assert_eq!(negabase::parse("162", -10).unwrap(), BigRational::from_integer(42.into()));
+        assert_eq!(negabase::parse("1.1", -2).unwrap(), BigRational::new(1.into(), 2.into()));
+        assert!(negabase::parse("12", -2).is_err());
+        assert!(negabase::parse("-1", -2).is_err());
+    }
+
+    #[test]
+    fn negabase_format_1_() {
+        assert_eq!(negabase::format(&BigRational::from_integer(42.into()), -2, None), "1111110");
+        assert_eq!(negabase::format(&BigRational::from_integer((-42).into()), -10, None), "58");
+        assert_eq!(negabase::format(&BigRational::from_integer(0.into()), -2, None), "0");
+        assert_eq!(negabase::format(&BigRational::new(1.into(), 2.into()), -2, None), "1.1");
+        assert_eq!(negabase::format(&BigRational::new((-3).into(), 4.into()), -2, None), "11.01");
+    }
+
+    #[test]
+    fn complex_format_1_() {
+        let number = |re: i32, im: i32| Complex::new(BigRational::from_integer(re.into()), BigRational::from_integer(im.into()));
+        assert_eq!(complex::format(&number(3, 4), ComplexBase::QuaterImaginary, None), "23");
+        assert_eq!(complex::format(&number(4, 0), ComplexBase::QuaterImaginary, None), "10300");
+        assert_eq!(complex::format(&number(-1, 0), ComplexBase::QuaterImaginary, None), "103");
+        assert_eq!(complex::format(&number(0, 1), ComplexBase::QuaterImaginary, None), "10.2");
+        assert_eq!(complex::format(&number(3, 4), ComplexBase::MinusOnePlusI, None), "1111101");
+        assert_eq!(complex::format(&number(2, 0), ComplexBase::MinusOnePlusI, None), "1100");
+        assert_eq!(complex::format(&number(0, -1), ComplexBase::MinusOnePlusI, None), "111");
+    }
+
+    #[test]
+    fn complex_parse_1_() {
+        let number = |re: i32, im: i32| Complex::new(BigRational::from_integer(re.into()), BigRational::from_integer(im.into()));
+        assert_eq!(complex::parse("1101", ComplexBase::QuaterImaginary).unwrap(), number(-3, -8));
+        assert_eq!(complex::parse("10.2", ComplexBase::QuaterImaginary).unwrap(), number(0, 1));
+        assert_eq!(complex::parse("11101", ComplexBase::MinusOnePlusI).unwrap(), number(-1, 0));
+        assert!(complex::parse("4", ComplexBase::QuaterImaginary).is_err());
+        assert!(complex::parse("2", ComplexBase::MinusOnePlusI).is_err());
+    }
+
+    #[test]
+    fn complex_parse_literal_1_() {
+        let number = |re: i32, im: i32| Complex::new(BigRational::from_integer(re.into()), BigRational::from_integer(im.into()));
+        assert_eq!(complex::parse_literal("3+4i", 10, &STANDARD).unwrap(), number(3, 4));
+        assert_eq!(complex::parse_literal("-3-4i", 10, &STANDARD).unwrap(), number(-3, -4));
+        assert_eq!(complex::parse_literal("-i", 10, &STANDARD).unwrap(), number(0, -1));
+        assert_eq!(complex::parse_literal("11i", 2, &STANDARD).unwrap(), number(0, 3));
+        assert_eq!(complex::parse_literal("7", 10, &STANDARD).unwrap(), number(7, 0));
+        assert!(complex::parse_literal("3+4", 10, &STANDARD).is_err());
+    }
+
+    #[test]
+    fn factoradic_1_() {
+        assert_eq!(factoradic::format(&463.into()), "341010");
+        assert_eq!(factoradic::format(&(-7).into()), "-1010");
+        assert_eq!(factoradic::format(&0.into()), "0");
+        assert_eq!(factoradic::parse("341010").unwrap(), 463.into());
+        assert_eq!(factoradic::parse("1:0:0").unwrap(), 2.into());
+        assert!(factoradic::parse("20").is_err());
+        assert!(factoradic::parse("1").is_err());
+    }
+
+    #[test]
+    fn factoradic_permutation_1_() {
+        let code = factoradic::lehmer_code(&463.into(), Some(6)).unwrap();
+        assert_eq!(code, vec![3, 4, 1, 0, 1, 0]);
+        assert_eq!(factoradic::permutation(&code), vec![4, 6, 2, 1, 5, 3]);
+        assert_eq!(factoradic::rank(&[4, 6, 2, 1, 5, 3]).unwrap(), 463.into());
+        assert_eq!(factoradic::lehmer_code(&5.into(), None).unwrap(), vec![2, 1, 0]);
+        assert!(factoradic::lehmer_code(&720.into(), Some(6)).is_none());
+        assert!(factoradic::lehmer_code(&463.into(), Some(factoradic::MAX_ELEMENTS + 1)).is_none());
+        assert!(factoradic::rank(&[1, 3, 3]).is_none());
+    }
+
+    #[test]
+    fn zeckendorf_1_() {
+        assert_eq!(zeckendorf::format(&100.into()).unwrap(), "1000010100");
+        assert_eq!(zeckendorf::format(&0.into()).unwrap(), "0");
+        assert!(zeckendorf::format(&(-1).into()).is_none());
+        assert_eq!(zeckendorf::parse("1000010100").unwrap(), 100.into());
+        assert!(zeckendorf::parse("1011").is_err());
+        assert!(zeckendorf::parse("102").is_err());
+    }
+
+    #[test]
+    fn zeckendorf_code_1_() {
+        assert_eq!(zeckendorf::format_code(&100.into()).unwrap(), "00101000011");
+        assert_eq!(zeckendorf::format_code(&1.into()).unwrap(), "11");
+        assert!(zeckendorf::format_code(&0.into()).is_none());
+        assert_eq!(zeckendorf::parse_code("00101000011").unwrap(), 100.into());
+        assert!(zeckendorf::parse_code("0010100001").is_err());
+        assert!(zeckendorf::parse_code("110011").is_err());
+    }
+
+    #[test]
+    fn roman_1_() {
+        assert_eq!(roman::format(&1994.into()).unwrap(), "MCMXCIV");
+        assert_eq!(roman::format(&3999.into()).unwrap(), "MMMCMXCIX");
+        assert_eq!(roman::format(&5010.into()).unwrap(), "V\u{305}X");
+        assert_eq!(roman::format(&0.into()).unwrap(), "N");
+        assert!(roman::format(&4_000_000.into()).is_none());
+        assert!(roman::format(&(-1).into()).is_none());
+    }
+
+    #[test]
+    fn roman_parse_1_() {
+        assert_eq!(roman::parse("MCMXCIV", true).unwrap(), 1994.into());
+        assert_eq!(roman::parse("mcmxciv", true).unwrap(), 1994.into());
+        assert_eq!(roman::parse("I\u{305}V\u{305}", true).unwrap(), 4000.into());
+        assert_eq!(roman::parse("CCIↃↃ", true).unwrap(), 10000.into());
+        assert_eq!(roman::parse("CIↃIↃ", true).unwrap(), 1500.into());
+        assert_eq!(roman::parse("IIII", false).unwrap(), 4.into());
+        assert_eq!(roman::parse("IM", false).unwrap(), 999.into());
+        assert!(roman::parse("IIII", true).is_err());
+        assert!(roman::parse("IM", true).is_err());
+        assert!(roman::parse("MCMB", false).is_err());
+    }
+
+    #[test]
+    fn bijective_1_() {
+        assert_eq!(bijective::parse("XFD", 26, true).unwrap(), 16384.into());
+        assert_eq!(bijective::parse("az", 26, true).unwrap(), 52.into());
+        assert_eq!(bijective::parse("1A", 10, false).unwrap(), 20.into());
+        assert!(bijective::parse("10", 10, false).is_err());
+        assert!(bijective::parse("A1", 26, true).is_err());
+        assert_eq!(bijective::format(&16384.into(), 26, true).unwrap(), "XFD");
+        assert_eq!(bijective::format(&27.into(), 26, true).unwrap(), "AA");
+        assert_eq!(bijective::format(&20.into(), 10, false).unwrap(), "1A");
+        assert_eq!(bijective::format(&6.into(), 2, false).unwrap(), "22");
+        assert!(bijective::format(&0.into(), 26, true).is_none());
+    }
+
+    #[test]
+    fn mixed_radix_1_() {
+        let days = MixedRadix::from_suffix("[24,60,60]").unwrap();
+        let number = |num: i32| BigRational::from_integer(num.into());
+        assert_eq!(days.format(&number(100000), None, RoundingMode::TowardZero).0, "1:03:46:40");
+        assert_eq!(days.format(&number(0), None, RoundingMode::TowardZero).0, "0:00:00:00");
+        assert_eq!(days.format(&number(-61), None, RoundingMode::TowardZero).0, "-0:00:01:01");
+        assert_eq!(MixedRadix::from_suffix("hms").unwrap().format(&BigRational::new(3.into(), 2.into()), None, RoundingMode::TowardZero).0, "0:00:01.5");
+        assert_eq!(days.parse("1:03:46:40").unwrap(), number(100000));
+        assert_eq!(days.parse("46:40").unwrap(), number(2800));
+        assert_eq!(days.parse("-1:0").unwrap(), number(-60));
+        assert!(days.parse("1:60").is_err());
+        assert!(days.parse("1.5:00").is_err());
+        assert!(days.parse("1:2:3:4:5").is_err());
+        assert!(MixedRadix::from_suffix("[24,1]").is_none());
+        assert!(MixedRadix::from_suffix("[]").is_none());
+    }
+
+    #[test]
+    fn scientific_1_() {
+        assert_eq!(converting::split_exponent("1.01p+5", 2, &STANDARD), Some(("1.01", "+5")));
+        assert_eq!(converting::split_exponent("6.02e23", 10, &STANDARD), Some(("6.02", "23")));
+        assert_eq!(converting::split_exponent("1E3", 16, &STANDARD), None);
+        assert_eq!(converting::split_exponent("1e", 10, &STANDARD), None);
+        assert_eq!(converting::parse_number("1.01p+5", 2, &STANDARD).unwrap(), BigRational::from_integer(40.into()));
+        assert_eq!(converting::parse_number("25e-2", 10, &STANDARD).unwrap(), BigRational::new(1.into(), 4.into()));
+        assert!(converting::parse_number("1e2e3", 10, &STANDARD).is_err());
+        assert_eq!(converting::format_scientific(BigRational::from_integer(40.into()), 2, None, RoundingMode::TowardZero, &STANDARD).0, "1.01e+5");
+        assert_eq!(converting::format_scientific(BigRational::new(1.into(), 256.into()), 16, None, RoundingMode::TowardZero, &STANDARD).0, "1p-2");
+    }
+
+    #[test]
+    fn ieee754_encode_1_() {
+        let decimal = |num: &str| converting::parse_number(num, 10, &STANDARD).unwrap();
+        let hex = |bits: &str| BigUint::parse_bytes(bits.as_bytes(), 16).unwrap();
+        assert_eq!(ieee754::SINGLE.encode(&decimal("3.14159")), hex("40490FD0"));
+        assert_eq!(ieee754::SINGLE.encode(&decimal("-2")), hex("C0000000"));
+        assert_eq!(ieee754::HALF.encode(&decimal("0.1")), hex("2E66"));
+        assert_eq!(ieee754::HALF.encode(&decimal("65519")), hex("7BFF"));
+        assert_eq!(ieee754::HALF.encode(&decimal("65520")), hex("7C00"));
+        assert_eq!(ieee754::HALF.encode(&decimal("0.0000000596046447753906250")), hex("0001"));
+        assert_eq!(ieee754::HALF.encode(&decimal("0.0000000298023223876953125")), hex("0000"));
+        assert_eq!(ieee754::DOUBLE.encode(&decimal("0.1")), hex("3FB999999999999A"));
+    }
+
+    #[test]
+    fn ieee754_decode_1_() {
+        let hex = |bits: &str| BigUint::parse_bytes(bits.as_bytes(), 16).unwrap();
+        match ieee754::SINGLE.decode(&hex("40490FDB")) {
+            (false, FloatValue::Finite(value)) => {
+                assert_eq!(converting::format_fractional(value, 10, Some(30), &STANDARD), "3.1415927410125732421875")
+            }
+            _ => panic!(),
+        }
+        assert!(matches!(ieee754::HALF.decode(&hex("FC00")), (true, FloatValue::Infinity)));
+        match ieee754::HALF.decode(&hex("7D01")) {
+            (false, FloatValue::NaN { quiet, payload }) => assert!(!quiet && payload == BigUint::from(0x101u32)),
+            _ => panic!(),
+        }
+        assert!(ieee754::HALF.parse_bits("1FFFF").is_err());
+        assert_eq!(ieee754::HALF.parse_bits("0b11").unwrap(), BigUint::from(3u32));
+    }
+
+    #[test]
+    fn ieee754_encode_2_() {
+        let decimal = |num: &str| converting::parse_number(num, 10, &STANDARD).unwrap();
+        assert_eq!(ieee754::BFLOAT16.encode(&decimal("3.14159")), BigUint::from(0x4049u32));
+        assert_eq!(ieee754::TENSOR_FLOAT32.encode(&BigRational::new(1.into(), 3.into())), BigUint::from(0x1F555u32));
+        assert_eq!(ieee754::E4M3.encode(&decimal("448")), BigUint::from(0x7Eu32));
+        assert_eq!(ieee754::E4M3.encode(&decimal("500")), BigUint::from(0x7Eu32));
+        assert_eq!(ieee754::E4M3.encode(&decimal("-1000000")), BigUint::from(0xFEu32));
+        assert_eq!(ieee754::E4M3.encode(&decimal("0.001953125")), BigUint::from(0x01u32));
+        assert_eq!(ieee754::E5M2.encode(&decimal("100000")), BigUint::from(0x7Bu32));
+        assert_eq!(ieee754::E5M2.encode(&decimal("1.25")), BigUint::from(0x3Du32));
+        assert_eq!(ieee754::E5M2.encode(&decimal("1.125")), BigUint::from(0x3Cu32));
+        assert_eq!(ieee754::E5M2.encode(&decimal("1.375")), BigUint::from(0x3Eu32));
+    }
+
+    #[test]
+    fn ieee754_decode_2_() {
+        assert!(matches!(ieee754::E4M3.decode(&BigUint::from(0x7Fu32)), (false, FloatValue::NaN { .. })));
+        assert!(matches!(ieee754::E4M3.decode(&BigUint::from(0xFFu32)), (true, FloatValue::NaN { .. })));
+        assert!(matches!(ieee754::E5M2.decode(&BigUint::from(0x7Cu32)), (false, FloatValue::Infinity)));
+        match ieee754::E4M3.decode(&BigUint::from(0x78u32)) {
+            (false, FloatValue::Finite(value)) => assert_eq!(value, BigRational::from_integer(256.into())),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn fixed_width_1_() {
+        let i8 = IntegerWidth::from_suffix("i8").unwrap();
+        let u8 = IntegerWidth::from_suffix("u8").unwrap();
+        assert_eq!(i8.twos_complement(&(-5).into()).unwrap(), BigUint::from(0xFBu32));
+        assert_eq!(i8.ones_complement(&(-5).into()).unwrap(), BigUint::from(0xFAu32));
+        assert_eq!(i8.sign_magnitude(&(-5).into()).unwrap(), BigUint::from(0x85u32));
+        assert_eq!(i8.twos_complement(&(-128).into()).unwrap(), BigUint::from(0x80u32));
+        assert!(i8.ones_complement(&(-128).into()).is_none());
+        assert!(i8.sign_magnitude(&(-128).into()).is_none());
+        assert!(i8.twos_complement(&128.into()).is_none());
+        assert_eq!(u8.twos_complement(&255.into()).unwrap(), BigUint::from(0xFFu32));
+        assert!(u8.twos_complement(&(-1).into()).is_none());
+        assert!(IntegerWidth::from_suffix("i0").is_none());
+        assert!(IntegerWidth::from_suffix("f8").is_none());
+    }
+
+    #[test]
+    fn fixed_width_2_() {
+        let i8 = IntegerWidth::from_suffix("i8").unwrap();
+        let pattern = BigUint::from(0xFBu32);
+        assert_eq!(i8.read_twos_complement(&pattern), (-5).into());
+        assert_eq!(i8.read_ones_complement(&pattern), (-4).into());
+        assert_eq!(i8.read_sign_magnitude(&pattern), (-123).into());
+        assert_eq!(IntegerWidth::from_suffix("u8").unwrap().read_twos_complement(&pattern), 251.into());
+        assert_eq!(i8.read_ones_complement(&BigUint::from(0xFFu32)), 0.into());
+    }
+
+    #[test]
+    fn fixed_point_1_() {
+        let q4_12 = QFormat::from_suffix("Q4.12").unwrap();
+        let decimal = |num: &str| converting::parse_number(num, 10, &STANDARD).unwrap();
+        assert_eq!(q4_12.encode(&decimal("3.14159")), (12868.into(), false));
+        assert_eq!(q4_12.encode(&decimal("-1.5")), ((-6144).into(), false));
+        assert_eq!(q4_12.encode(&decimal("100")), (32767.into(), true));
+        assert_eq!(q4_12.encode(&decimal("-100")), ((-32768).into(), true));
+        assert_eq!(q4_12.encode(&decimal("0.0003662109375")), (2.into(), false));
+        assert_eq!(q4_12.parse_raw("3244").unwrap(), 12868.into());
+        assert_eq!(q4_12.parse_raw("E800").unwrap(), (-6144).into());
+        assert_eq!(q4_12.value(&12868.into()), decimal("3.1416015625"));
+        assert!(QFormat::from_suffix("UQ0.8").is_some());
+        assert!(QFormat::from_suffix("Q0.8").is_none());
+        assert!(QFormat::from_suffix("Q4").is_none());
+    }
+
+    #[test]
+    fn bcd_1_() {
+        assert_eq!(DecimalCode::Packed.encode(&1994.into()).unwrap(), vec![1, 9, 9, 4]);
+        assert_eq!(DecimalCode::Excess3.encode(&1994.into()).unwrap(), vec![4, 12, 12, 7]);
+        assert_eq!(DecimalCode::Aiken.encode(&1994.into()).unwrap(), vec![1, 15, 15, 4]);
+        assert!(DecimalCode::Packed.encode(&(-1).into()).is_none());
+        assert_eq!(DecimalCode::Aiken.decode(&[1, 15, 15, 4]).unwrap(), 1994.into());
+        assert!(DecimalCode::Packed.decode(&[1, 10]).is_err());
+        assert!(DecimalCode::Excess3.decode(&[2]).is_err());
+        assert!(DecimalCode::Aiken.decode(&[7]).is_err());
+    }
+
+    #[test]
+    fn bcd_parse_nibbles_1_() {
+        assert_eq!(DecimalCode::Packed.parse_nibbles("0x1994").unwrap(), vec![1, 9, 9, 4]);
+        assert_eq!(DecimalCode::Packed.parse_nibbles("0b11001").unwrap(), vec![1, 9]);
+        assert_eq!(DecimalCode::Unpacked.parse_nibbles("0x01090904").unwrap(), vec![1, 9, 9, 4]);
+        assert_eq!(DecimalCode::Unpacked.parse_nibbles("109").unwrap(), vec![1, 9]);
+        assert!(DecimalCode::Unpacked.parse_nibbles("0x3139").is_err());
+        assert!(DecimalCode::Packed.parse_nibbles("0b102").is_err());
+        assert!(DecimalCode::Packed.parse_nibbles("0xG1").is_err());
+    }
+
+    #[test]
+    fn gray_1_() {
+        let binary: Vec<String> = (0..8).map(|num| gray::encode(&num.into(), 2).unwrap()).collect();
+        assert_eq!(binary, vec!["0", "1", "11", "10", "110", "111", "101", "100"]);
+        let ternary: Vec<String> = (0..10).map(|num| gray::encode(&num.into(), 3).unwrap()).collect();
+        assert_eq!(ternary, vec!["0", "1", "2", "12", "11", "10", "20", "21", "22", "122"]);
+        assert_eq!(gray::encode(&19.into(), 10).unwrap(), "10");
+        assert!(gray::encode(&(-1).into(), 2).is_none());
+    }
+
+    #[test]
+    fn gray_2_() {
+        assert_eq!(gray::decode("1011", 2).unwrap(), 13.into());
+        assert_eq!(gray::decode("122", 3).unwrap(), 9.into());
+        assert_eq!(gray::decode("190", 10).unwrap(), 100.into());
+        assert!(gray::decode("12", 2).is_err());
+        assert!(gray::decode("", 2).is_err());
+    }
+
+    #[test]
+    fn format_rounded_1_() {
+        let two_thirds = BigRational::new(2.into(), 3.into());
+        let round = |value: &BigRational, length, mode| converting::format_rounded(value.clone(), 10, Some(length), mode, &STANDARD);
+
+        assert_eq!(round(&two_thirds, 3, RoundingMode::TowardZero), ("0.666".to_owned(), Ordering::Less));
+        assert_eq!(round(&two_thirds, 3, RoundingMode::HalfEven), ("0.667".to_owned(), Ordering::Greater));
+        assert_eq!(round(&-two_thirds.clone(), 3, RoundingMode::Ceiling), ("-0.666".to_owned(), Ordering::Greater));
+        assert_eq!(round(&-two_thirds.clone(), 3, RoundingMode::Floor), ("-0.667".to_owned(), Ordering::Less));
+        assert_eq!(round(&-two_thirds, 0, RoundingMode::HalfUp), ("-1".to_owned(), Ordering::Less));
+
+        let tie = BigRational::new(125.into(), 1000.into());
+        assert_eq!(round(&tie, 2, RoundingMode::HalfEven), ("0.12".to_owned(), Ordering::Less));
+        assert_eq!(round(&tie, 2, RoundingMode::HalfUp), ("0.13".to_owned(), Ordering::Greater));
+        assert_eq!(round(&tie, 5, RoundingMode::Floor), ("0.125".to_owned(), Ordering::Equal));
+        assert_eq!(round(&-tie, 1, RoundingMode::TowardZero), ("-0.1".to_owned(), Ordering::Greater));
+        assert_eq!(RoundingMode::from_name("HalfEven"), Some(RoundingMode::HalfEven));
+        assert_eq!(RoundingMode::from_name("nearest"), None);
+    }
+
+    #[test]
+    fn record_steps_1_() {
+        let mut steps = Steps::new(2, 16, &STANDARD);
+        converting::any_fractional_to_any_periodic("-1101.101", 2, 16, 50, &STANDARD, &STANDARD, Some(&mut steps)).unwrap();
+
+        assert!(steps.negative);
+        assert_eq!(steps.terms, vec![(1, 3), (1, 2), (0, 1), (1, 0), (1, -1), (0, -2), (1, -3)]);
+        assert_eq!(steps.value, BigRational::new((-109).into(), 8.into()));
+        assert_eq!(steps.divisions.len(), 1);
+        assert_eq!(steps.divisions[0].remainder, 13);
+        assert_eq!(steps.multiplications.len(), 1);
+        assert_eq!(steps.multiplications[0].digit, 10);
+        assert!(steps.period_start.is_none() && steps.rounding.is_none());
+
+        let mut steps = Steps::new(10, 2, &STANDARD);
+        converting::any_fractional_to_any_rounded("0.(3)", 10, 2, Some(4), RoundingMode::HalfUp, &STANDARD, &STANDARD, Some(&mut steps)).unwrap();
+        assert!(steps.terms.is_empty());
+        assert_eq!(steps.multiplications.iter().map(|step| step.digit).collect::<Vec<_>>(), vec![0, 1, 0, 1]);
+        assert_eq!(steps.rounding, Some(RoundingMode::HalfUp));
+
+        let mut steps = Steps::new(10, 2, &STANDARD);
+        converting::any_fractional_to_any_periodic("0.(3)", 10, 2, 50, &STANDARD, &STANDARD, Some(&mut steps)).unwrap();
+        assert_eq!(steps.multiplications.len(), 2);
+        assert_eq!(steps.period_start, Some(0));
+
+        let mut steps = Steps::new(16, 2, &STANDARD);
+        assert_eq!(converting::any_whole_to_any("-A", 16, 2, &STANDARD, &STANDARD, Some(&mut steps)).unwrap(), "-1010");
+        assert_eq!(steps.terms, vec![(10, 0)]);
+        assert_eq!(steps.divisions.iter().map(|step| step.remainder).collect::<Vec<_>>(), vec![0, 1, 0, 1]);
+
+        let mut steps = Steps::new(10, 2, &STANDARD);
+        assert!(converting::any_fractional_to_any_periodic("0.1", 10, 2, 3, &STANDARD, &STANDARD, Some(&mut steps)).unwrap().is_none());
+        assert!(steps.divisions.is_empty() && steps.multiplications.is_empty());
+    }
+
+    #[test]
+    fn fixed_width_3_() {
+        let i8 = IntegerWidth::from_suffix("i8").unwrap();
+
+        assert_eq!(i8.wrap(&200.into()), (-56).into());
+        assert_eq!(i8.wrap(&(-129).into()), 127.into());
+        assert_eq!(i8.rotate_left(&BigUint::from(0b1000_0001u32), 1), BigUint::from(0b0000_0011u32));
+        assert_eq!(i8.rotate_left(&BigUint::from(0b1000_0001u32), 15), BigUint::from(0b1100_0000u32));
+        assert_eq!(i8.reverse(&BigUint::from(0b0000_0110u32)), BigUint::from(0b0110_0000u32));
+    }
+}
+
+mod converter_test {
+    use crate::converter::{Converter, ValidErrorKind};
+    use crate::digits::converting::RoundingMode;
+
+    #[test]
+    fn convert_from_message_1_() {
+        assert_eq!(Converter::convert_from_message("-1011_2 > 10").result.unwrap(), "-11");
+        assert_eq!(Converter::convert_from_message("-0.75_10 > 2").result.unwrap(), "-0.11");
+        assert_eq!(Converter::convert_from_message("0_10 > 2").result.unwrap(), "0");
+    }
+
+    #[test]
+    fn convert_from_message_2_() {
+        assert_eq!(Converter::convert_from_message("0.(3)_10 > 3").result.unwrap(), "0.1");
+        assert_eq!(
+            Converter::convert_from_message("0.1_10 > 2").result.unwrap(),
+            "0.0(0011)\nPre-period length: 1, period length: 4"
+        );
+        assert_eq!(Converter::convert_from_message("0.1_10 > 8_2").result.unwrap(), "0.00011001\nRounded down");
+    }
+
+    #[test]
+    fn convert_from_message_3_() {
+        assert!(Converter::convert_from_message("-_10 > 2").error_kind == Some(ValidErrorKind::InvalidInitialNumber));
+        assert!(Converter::convert_from_message("1-1_10 > 2").error_kind == Some(ValidErrorKind::InvalidInitialNumber));
+        assert!(Converter::convert_from_message("0.5_10 > -1_2").error_kind == Some(ValidErrorKind::InvalidMantissaLength));
+        assert!(Converter::convert_from_message("0.5_10 > 51_2").error_kind == Some(ValidErrorKind::InvalidMantissaLength));
+        assert!(Converter::convert_from_message("1_10 / 3_10 > -1_2").error_kind == Some(ValidErrorKind::InvalidMantissaLength));
+        assert!(Converter::convert_from_message("0.()_10 > 2").error_kind == Some(ValidErrorKind::InvalidInitialNumber));
+        assert!(Converter::convert_from_message("0.1()_10 > 2").error_kind == Some(ValidErrorKind::InvalidInitialNumber));
+        assert!(Converter::convert_from_message("0.()1_10 > 2").error_kind == Some(ValidErrorKind::InvalidInitialNumber));
+        assert!(Converter::convert_from_message("0.(3)1_10 > 2").error_kind == Some(ValidErrorKind::InvalidInitialNumber));
+        assert!(Converter::convert_from_message("(3).1_10 > 2").error_kind == Some(ValidErrorKind::InvalidInitialNumber));
+    }
+
+    #[test]
+    fn convert_from_message_4_() {
+        assert_eq!(Converter::convert_from_message("zz_62 > 10").result.unwrap(), "3843");
+        assert_eq!(Converter::convert_from_message("97_10 > 58btc").result.unwrap(), "2g");
+        assert_eq!(Converter::convert_from_message("255_10 > 64").result.unwrap(), "D/");
+        assert!(Converter::convert_from_message("10_63 > 2").error_kind == Some(ValidErrorKind::InvalidInitialBase));
+        assert!(Converter::convert_from_message("Il_58btc > 10").error_kind == Some(ValidErrorKind::InvalidInitialNumber));
+        assert!(Converter::convert_from_message("10_10 > 65").error_kind == Some(ValidErrorKind::InvalidConditionBase));
+    }
+
+    #[test]
+    fn convert_from_message_5_() {
+        assert_eq!(Converter::convert_from_message("GATTACA_{ACGT} > 10").result.unwrap(), "9156");
+        assert_eq!(Converter::convert_from_message("9156_10 > {ACGT}").result.unwrap(), "GATTACA");
+        assert_eq!(Converter::convert_from_message("1↊_{0123456789↊↋} > 10").result.unwrap(), "22");
+        assert_eq!(Converter::convert_from_message("0.8(3)_10 > {0123456789↊↋}").result.unwrap(), "0.↊");
+        assert!(Converter::convert_from_message("10_{AA} > 2").error_kind == Some(ValidErrorKind::InvalidAlphabet));
+        assert!(Converter::convert_from_message("10_10 > {A.B}").error_kind == Some(ValidErrorKind::InvalidAlphabet));
+    }
+
+    #[test]
+    fn convert_from_message_6_() {
+        assert_eq!(Converter::convert_from_message("1T0_bal3 > 10").result.unwrap(), "6");
+        assert_eq!(Converter::convert_from_message("-01_bal3 > 10").result.unwrap(), "-8");
+        assert_eq!(Converter::convert_from_message("-8_10 > bal3").result.unwrap(), "T01");
+        assert_eq!(Converter::convert_from_message("42_10 > bal5").result.unwrap(), "22\u{305}2");
+        assert_eq!(
+            Converter::convert_from_message("0.1_bal3 > 10").result.unwrap(),
+            "0.(3)\nPre-period length: 0, period length: 1"
+        );
+        assert!(Converter::convert_from_message("12_bal3 > 10").error_kind == Some(ValidErrorKind::InvalidInitialNumber));
+        assert!(Converter::convert_from_message("1_bal4 > 10").error_kind == Some(ValidErrorKind::InvalidInitialBase));
+    }
+
+    #[test]
+    fn convert_from_message_7_() {
+        assert_eq!(Converter::convert_from_message("1101_-2 > 10").result.unwrap(), "-3");
+        assert_eq!(Converter::convert_from_message("42_10 > -2").result.unwrap(), "1111110");
+        assert_eq!(Converter::convert_from_message("-0.75_10 > -2").result.unwrap(), "11.01");
+        assert_eq!(Converter::convert_from_message("1111110_-2 > -10").result.unwrap(), "162");
+        assert!(Converter::convert_from_message("1_-37 > 10").error_kind == Some(ValidErrorKind::InvalidInitialBase));
+        assert!(Converter::convert_from_message("2_-2 > 10").error_kind == Some(ValidErrorKind::InvalidInitialNumber));
+    }
+
+    #[test]
+    fn convert_from_message_8_() {
+        assert_eq!(Converter::convert_from_message("3+4i_10 > 2i").result.unwrap(), "23");
+        assert_eq!(Converter::convert_from_message("3+4i_10 > -1+i").result.unwrap(), "1111101");
+        assert_eq!(Converter::convert_from_message("1101_2i > 10").result.unwrap(), "-3-8i");
+        assert_eq!(Converter::convert_from_message("10.2_2i > 10").result.unwrap(), "1i");
+        assert_eq!(Converter::convert_from_message("11_-1+i > 2i").result.unwrap(), "10.2");
+        assert_eq!(Converter::convert_from_message("11101_-1+i > 2").result.unwrap(), "-1");
+        assert!(Converter::convert_from_message("4_2i > 10").error_kind == Some(ValidErrorKind::InvalidInitialNumber));
+        assert!(Converter::convert_from_message("10_2i > bal3").error_kind == Some(ValidErrorKind::InvalidConditionBase));
+    }
+
+    #[test]
+    fn convert_from_message_9_() {
+        assert_eq!(Converter::convert_from_message("463_10 > !").result.unwrap(), "341010");
+        assert_eq!(Converter::convert_from_message("341010_! > 16").result.unwrap(), "1CF");
+        assert_eq!(
+            Converter::convert_from_message("463_10 > perm6").result.unwrap(),
+            "4,6,2,1,5,3\nLehmer code: 3,4,1,0,1,0"
+        );
+        assert_eq!(
+            Converter::convert_from_message("2_10 > perm4").result.unwrap(),
+            "1,3,2,4\nLehmer code: 0,1,0,0"
+        );
+        assert_eq!(Converter::convert_from_message("4,6,2,1,5,3_perm > 10").result.unwrap(), "463");
+        assert!(Converter::convert_from_message("0.5_10 > !").error_kind == Some(ValidErrorKind::UnrepresentableNumber));
+        assert!(Converter::convert_from_message("720_10 > perm6").error_kind == Some(ValidErrorKind::UnrepresentableNumber));
+        assert!(Converter::convert_from_message("463_10 > perm100000").error_kind == Some(ValidErrorKind::InvalidCondition));
+        assert!(Converter::convert_from_message("463_10 > perm1000000000").error_kind == Some(ValidErrorKind::InvalidCondition));
+        assert!(Converter::convert_from_message("2_! > 10").error_kind == Some(ValidErrorKind::InvalidInitialNumber));
+    }
+
+    #[test]
+    fn convert_from_message_10_() {
+        assert_eq!(Converter::convert_from_message("100_10 > fib").result.unwrap(), "1000010100");
+        assert_eq!(Converter::convert_from_message("1000010100_fib > 16").result.unwrap(), "64");
+        assert_eq!(Converter::convert_from_message("4_10 > fibcode").result.unwrap(), "1011");
+        assert_eq!(Converter::convert_from_message("1011_fibcode > fib").result.unwrap(), "101");
+        assert!(Converter::convert_from_message("1011_fib > 10").error_kind == Some(ValidErrorKind::InvalidZeckendorfNumber));
+        assert!(Converter::convert_from_message("101_fibcode > 10").error_kind == Some(ValidErrorKind::InvalidZeckendorfNumber));
+        assert!(Converter::convert_from_message("-4_10 > fib").error_kind == Some(ValidErrorKind::UnrepresentableNumber));
+    }
+
+    #[test]
+    fn convert_from_message_11_() {
+        assert_eq!(Converter::convert_from_message("MCMXCIV > 16").result.unwrap(), "7CA");
+        assert_eq!(Converter::convert_from_message("1994_10 > roman").result.unwrap(), "MCMXCIV");
+        assert_eq!(Converter::convert_from_message("IIII_romanlax > 10").result.unwrap(), "4");
+        assert_eq!(Converter::convert_from_message("IↃↃ > roman").result.unwrap(), "V\u{305}");
+        assert!(Converter::convert_from_message("IIII > 10").error_kind == Some(ValidErrorKind::InvalidInitialNumber));
+        assert!(Converter::convert_from_message("0.5_10 > roman").error_kind == Some(ValidErrorKind::UnrepresentableNumber));
+        assert!(Converter::convert_from_message("10 > 2").error_kind == Some(ValidErrorKind::InvalidInitialNumber));
+    }
+
+    #[test]
+    fn convert_from_message_12_() {
+        assert_eq!(Converter::convert_from_message("XFD_col > 10").result.unwrap(), "16384");
+        assert_eq!(Converter::convert_from_message("16384_10 > col").result.unwrap(), "XFD");
+        assert_eq!(Converter::convert_from_message("AZ_col > bij10").result.unwrap(), "52");
+        assert_eq!(Converter::convert_from_message("100_10 > bij10").result.unwrap(), "9A");
+        assert!(Converter::convert_from_message("0_10 > col").error_kind == Some(ValidErrorKind::UnrepresentableNumber));
+        assert!(Converter::convert_from_message("X0_col > 10").error_kind == Some(ValidErrorKind::InvalidInitialNumber));
+        assert!(Converter::convert_from_message("1_bij36 > 10").error_kind == Some(ValidErrorKind::InvalidInitialBase));
+    }
+
+    #[test]
+    fn convert_from_message_13_() {
+        assert_eq!(Converter::convert_from_message("100000_10 > [24,60,60]").result.unwrap(), "1:03:46:40");
+        assert_eq!(Converter::convert_from_message("1:03:46:40_[24,60,60] > 10").result.unwrap(), "100000");
+        assert_eq!(Converter::convert_from_message("100000_10 > hms").result.unwrap(), "27:46:40");
+        assert_eq!(Converter::convert_from_message("1:30_hms > [7,24,60,60]").result.unwrap(), "0:0:00:01:30");
+        assert_eq!(Converter::convert_from_message("12:30:36_dms > 10").result.unwrap(), "45036");
+        assert_eq!(Converter::convert_from_message("0.75_10 > 2_hms").result.unwrap(), "0:00:00.75\nExact");
+        assert_eq!(Converter::convert_from_message("59.999_10 > 2_hms").result.unwrap(), "0:00:59.99\nRounded down");
+        assert_eq!(Converter::convert_from_message("59.999_10 > 2_hms halfup").result.unwrap(), "0:01:00\nRounded up");
+        assert_eq!(Converter::convert_from_message("-59.999_10 > 2_hms floor").result.unwrap(), "-0:01:00\nRounded down");
+        assert_eq!(Converter::convert_from_message("0.125_10 > 2_hms halfeven").result.unwrap(), "0:00:00.12\nRounded down");
+        assert_eq!(Converter::convert_from_message("3599.5_10 > 0_hms ceiling").result.unwrap(), "1:00:00\nRounded up");
+        assert!(Converter::convert_from_message("1:75_hms > 10").error_kind == Some(ValidErrorKind::InvalidInitialNumber));
+        assert!(Converter::convert_from_message("1_10 > [24,x]").error_kind == Some(ValidErrorKind::InvalidCondition));
+    }
+
+    #[test]
+    fn convert_from_message_14_() {
+        assert_eq!(Converter::convert_from_message("1.01p+5_2 > 10").result.unwrap(), "4e+1\nExact");
+        assert_eq!(Converter::convert_from_message("6.02e23_10 > 16").result.unwrap(), "7.F7A7C42A34p+19\nRounded down");
+        assert_eq!(Converter::convert_from_message("1.5e3_10 > roman").result.unwrap(), "MD");
+        assert_eq!(Converter::convert_from_message("1e-3_10 > 2").result.unwrap(), "1.0000011e-10\nRounded down");
+        assert_eq!(Converter::convert_from_message("1000_10 > sci2").result.unwrap(), "1.111101e+9\nExact");
+        assert_eq!(Converter::convert_from_message("-0.001_10 > 4_sci10").result.unwrap(), "-1e-3\nExact");
+        assert_eq!(Converter::convert_from_message("1^3_36 > 36").result.unwrap(), "1^+3\nExact");
+        assert_eq!(Converter::convert_from_message("1e3_16 > 10").result.unwrap(), "483");
+        assert_eq!(Converter::convert_from_message("0.(3)_10 > 2_sci10").result.unwrap(), "3.33e-1\nRounded down");
+        assert_eq!(Converter::convert_from_message("0.(3)_10 > 2_sci10 ceiling").result.unwrap(), "3.34e-1\nRounded up");
+        assert_eq!(Converter::convert_from_message("-0.(3)_10 > 2_sci10 floor").result.unwrap(), "-3.34e-1\nRounded down");
+        assert_eq!(Converter::convert_from_message("0.(6)_10 > 2_sci10 halfeven").result.unwrap(), "6.67e-1\nRounded up");
+        assert_eq!(Converter::convert_from_message("9.995_10 > 2_sci10 halfup").result.unwrap(), "1e+1\nRounded up");
+        assert!(Converter::convert_from_message("1e5000_10 > 2").error_kind == Some(ValidErrorKind::InvalidInitialNumber));
+        let nested = format!("1{}_10 > 2", "e4096".repeat(40));
+        assert!(Converter::convert_from_message(&nested).error_kind == Some(ValidErrorKind::InvalidInitialNumber));
+    }
+
+    #[test]
+    fn convert_from_message_15_() {
+        assert_eq!(
+            Converter::convert_from_message("0x40490FDB > f32").result.unwrap(),
+            "Hex: 0x40490FDB\n\
+            Binary: 0 10000000 10010010000111111011011\n\
+            Sign: 0 = 0x0 (+)\n\
+            Exponent: 10000000 = 0x80 (2^1)\n\
+            Fraction: 10010010000111111011011 = 0x490FDB\n\
+            Value: 3.1415927410125732421875"
+        );
+        assert_eq!(
+            Converter::convert_from_message("-0.1_10 > f16").result.unwrap(),
+            "Hex: 0xAE66\n\
+            Binary: 1 01011 1001100110\n\
+            Sign: 1 = 0x1 (-)\n\
+            Exponent: 01011 = 0x0B (2^-4)\n\
+            Fraction: 1001100110 = 0x266\n\
+            Value: -0.0999755859375\n\
+            Rounding error: 0.0000244140625"
+        );
+        assert!(Converter::convert_from_message("0x0001 > f16").result.unwrap().contains("(subnormal, 2^-14)"));
+        assert!(Converter::convert_from_message("0x0000 > f16").result.unwrap().contains("Exponent: 00000 = 0x00 (zero)"));
+        assert!(Converter::convert_from_message("0x8000 > f16").result.unwrap().ends_with("Exponent: 00000 = 0x00 (zero)\nFraction: 0000000000 = 0x000\nValue: -0"));
+        assert!(Converter::convert_from_message("0x00000001 > f32").result.unwrap().ends_with(
+            "Value: 0.00000000000000000000000000000000000000000000140129846432481707092372958328991613128026194187651577175706828388979108268586060148663818836212158203125"
+        ));
+        assert!(Converter::convert_from_message("0x7FF8000000000001 > f64").result.unwrap().ends_with("Value: +NaN, quiet, payload 0x1"));
+        assert!(Converter::convert_from_message("0xFF800000 > f32").result.unwrap().ends_with("Value: -Infinity"));
+        assert!(Converter::convert_from_message("1e5000_10 > 2_f64").error_kind == Some(ValidErrorKind::InvalidInitialNumber));
+        assert!(Converter::convert_from_message("1e308_10 > f64").result.unwrap().contains("Value: 100000000000000001097906362944045541740492309677311846336810682903157585404911491537163328978494688899061249669721172515611590283743140088328307009198146046031271664502933027185697489699588559043338384466165001178426897626212945177628091195786707458122783970171784415105291802893207873272974885715430223118336\nRounding error: ≈1.0979063629440455417404923096773118463368e+291"));
+        assert_eq!(Converter::convert_from_message("40490FDB_f32 > 2").result.unwrap(), "11.0010010000111111011011");
+        assert_eq!(Converter::convert_from_message("0xFF > 10").result.unwrap(), "255");
+        assert_eq!(Converter::convert_from_message("0b101 > 10").result.unwrap(), "5");
+        assert!(Converter::convert_from_message("7F800000_f32 > 10").error_kind == Some(ValidErrorKind::InvalidInitialNumber));
+    }
+
+    #[test]
+    fn convert_from_message_16_() {
+        assert_eq!(
+            Converter::convert_from_message("500_10 > e4m3").result.unwrap(),
+            "Hex: 0x7E\n\
+            Binary: 0 1111 110\n\
+            Sign: 0 = 0x0 (+)\n\
+            Exponent: 1111 = 0xF (2^8)\n\
+            Fraction: 110 = 0x6\n\
+            Value: 448\n\
+            Rounding error: -52"
+        );
+        assert!(Converter::convert_from_message("3.14159_10 > bf16").result.unwrap().ends_with("Value: 3.140625\nRounding error: -0.000965"));
+        assert!(Converter::convert_from_message("0x7F > e4m3").result.unwrap().ends_with("Value: +NaN"));
+        assert!(Converter::convert_from_message("0xFC > e5m2").result.unwrap().ends_with("Value: -Infinity"));
+        assert!(Converter::convert_from_message("1e10_10 > f16").result.unwrap().ends_with("Value: +Infinity\nRounding error: overflow"));
+        assert!(Converter::convert_from_message("0x1F555 > tf32").result.unwrap().starts_with("Hex: 0x1F555\nBinary: 0 01111101 0101010101"));
+        assert!(Converter::convert_from_message("0x80000 > tf32").error_kind == Some(ValidErrorKind::InvalidInitialNumber));
+    }
+
+    #[test]
+    fn convert_from_message_17_() {
+        assert_eq!(
+            Converter::convert_from_message("-5_10 > 2 i8").result.unwrap(),
+            "Two's complement: 11111011\nOne's complement: 11111010\nSign-magnitude: 10000101"
+        );
+        assert_eq!(
+            Converter::convert_from_message("0xFB > 10 i8").result.unwrap(),
+            "Two's complement: -5\nOne's complement: -4\nSign-magnitude: -123"
+        );
+        assert_eq!(Converter::convert_from_message("255_10 > 16 u8").result.unwrap(), "FF");
+        assert_eq!(Converter::convert_from_message("5_10 > 16 u16").result.unwrap(), "0005");
+        assert_eq!(Converter::convert_from_message("0b101 > 10 u4").result.unwrap(), "5");
+        assert_eq!(
+            Converter::convert_from_message("-128_10 > 16 i8").result.unwrap(),
+            "Two's complement: 80\nOne's complement: overflow\nSign-magnitude: overflow"
+        );
+        assert!(Converter::convert_from_message("256_10 > 16 u8").error_kind == Some(ValidErrorKind::Overflow));
+        assert!(Converter::convert_from_message("0x1FF > 10 i8").error_kind == Some(ValidErrorKind::Overflow));
+        assert!(Converter::convert_from_message("0.5_10 > 2 i8").error_kind == Some(ValidErrorKind::UnrepresentableNumber));
+        assert!(Converter::convert_from_message("5_10 > 2 x8").error_kind == Some(ValidErrorKind::InvalidCondition));
+    }
+
+    #[test]
+    fn convert_from_message_18_() {
+        assert_eq!(
+            Converter::convert_from_message("3.14159_10 > Q4.12").result.unwrap(),
+            "Raw: 12868\nHex: 0x3244\nValue: 3.1416015625\nQuantization error: 0.0000115625"
+        );
+        assert_eq!(Converter::convert_from_message("0x3244 > Q4.12").result.unwrap(), "Raw: 12868\nHex: 0x3244\nValue: 3.1416015625");
+        assert_eq!(Converter::convert_from_message("E800_Q4.12 > 10").result.unwrap(), "-1.5");
+        assert_eq!(
+            Converter::convert_from_message("-2_10 > UQ0.8").result.unwrap(),
+            "Raw: 0\nHex: 0x00\nValue: 0\nQuantization error: 2\nSaturated: the number is out of range"
+        );
+        assert!(Converter::convert_from_message("0x13244 > Q4.12").error_kind == Some(ValidErrorKind::InvalidInitialNumber));
+    }
+
+    #[test]
+    fn convert_from_message_19_() {
+        assert_eq!(
+            Converter::convert_from_message("1994_10 > bcd").result.unwrap(),
+            "Binary: 0001 1001 1001 0100\nHex: 0x1994\nValue: 1994"
+        );
+        assert_eq!(
+            Converter::convert_from_message("42_10 > ubcd").result.unwrap(),
+            "Binary: 00000100 00000010\nHex: 0x0402\nValue: 42"
+        );
+        assert_eq!(
+            Converter::convert_from_message("0x4CCC > xs3").result.unwrap(),
+            "Binary: 0100 1100 1100 1100\nHex: 0x4CCC\nValue: 1999"
+        );
+        assert_eq!(Converter::convert_from_message("1FF4_aiken > 10").result.unwrap(), "1994");
+        assert_eq!(Converter::convert_from_message("1994_bcd > xs3").result.unwrap(), "Binary: 0100 1100 1100 0111\nHex: 0x4CC7\nValue: 1994");
+        assert!(Converter::convert_from_message("0x19A4 > bcd").error_kind == Some(ValidErrorKind::InvalidBcdNibble(0xA, 3)));
+        assert!(Converter::convert_from_message("1A_bcd > 10").error_kind == Some(ValidErrorKind::InvalidBcdNibble(0xA, 2)));
+        assert!(Converter::convert_from_message("0x0C_xs3 > 10").error_kind == Some(ValidErrorKind::InvalidBcdNibble(0, 1)));
+        assert!(Converter::convert_from_message("0x1109_ubcd > 10").error_kind == Some(ValidErrorKind::InvalidInitialNumber));
+        assert!(Converter::convert_from_message("-5_10 > bcd").error_kind == Some(ValidErrorKind::UnrepresentableNumber));
+    }
+
+    #[test]
+    fn convert_from_message_20_() {
+        assert_eq!(Converter::convert_from_message("13_10 > gray").result.unwrap(), "1011");
+        assert_eq!(Converter::convert_from_message("1011_gray > 10").result.unwrap(), "13");
+        assert_eq!(Converter::convert_from_message("9_10 > gray3").result.unwrap(), "122");
+        assert_eq!(Converter::convert_from_message("FF_16 > gray16").result.unwrap(), "F0");
+        assert_eq!(Converter::convert_from_message("1011_gray > gray").result.unwrap(), "1011");
+        assert!(Converter::convert_from_message("0.5_10 > gray").error_kind == Some(ValidErrorKind::UnrepresentableNumber));
+        assert!(Converter::convert_from_message("1_gray37 > 10").error_kind == Some(ValidErrorKind::InvalidInitialBase));
+    }
+
+    #[test]
+    fn convert_from_message_21_() {
+        assert_eq!(Converter::convert_from_message("0.(6)_10 > 3_10 halfeven").result.unwrap(), "0.667\nRounded up");
+        assert_eq!(Converter::convert_from_message("-0.(6)_10 > 3_10 ceiling").result.unwrap(), "-0.666\nRounded up");
+        assert_eq!(Converter::convert_from_message("0.125_10 > 2_10 halfup").result.unwrap(), "0.13\nRounded up");
+        assert_eq!(Converter::convert_from_message("0.5_10 > 3_2 floor").result.unwrap(), "0.1\nExact");
+        assert_eq!(Converter::convert_from_message("0.1_bal3 > 3_10 HalfEven").result.unwrap(), "0.333\nRounded down");
+        assert_eq!(
+            Converter::convert_with_rounding("0.1_10 > 8_2", RoundingMode::Ceiling).result.unwrap(),
+            "0.0001101\nRounded up"
+        );
+        assert_eq!(
+            Converter::convert_with_rounding("0.1_10 > 8_2 truncate", RoundingMode::Ceiling).result.unwrap(),
+            "0.00011001\nRounded down"
+        );
+        assert!(Converter::convert_from_message("0.5_10 > 3_2 nearest").error_kind == Some(ValidErrorKind::InvalidCondition));
+    }
+
+    #[test]
+    fn explain_from_message_1_() {
+        assert_eq!(
+            Converter::explain_from_message("1101.101_2 > 16", RoundingMode::default()).result.unwrap(),
+            "Positional expansion:
+1101.101 = 1·2³ + 1·2² + 0·2¹ + 1·2⁰ + 1·2⁻¹ + 0·2⁻² + 1·2⁻³
+= 13.625
+
+Whole part, repeated division by 16:
+Dividend | Quotient | Remainder
+---------+----------+----------
+13       | 0        | 13 (D)
+Remainders from the bottom up: D
+
+Fractional part, repeated multiplication by 16:
+Fraction | × 16 | Digit
+---------+------+-------
+0.625    | 10   | 10 (A)
+Whole parts from the top down: A
+
+Result: D.A"
+        );
+        assert_eq!(
+            Converter::explain_from_message("0.1_10 > 3_2", RoundingMode::default()).result.unwrap(),
+            "Positional expansion:
+0.1 = 0·10⁰ + 1·10⁻¹
+= 0.1
+
+The whole part is 0
+
+Fractional part, repeated multiplication by 2:
+Fraction | × 2 | Digit
+---------+-----+------
+0.1      | 0.2 | 0
+0.2      | 0.4 | 0
+0.4      | 0.8 | 0
+Whole parts from the top down: 000
+The digits go on, so they are cut after 3 digits (rounding mode: truncate)
+
+Result: 0
+Rounded down"
+        );
+        assert!(Converter::explain_from_message("-0.(3)_10 > 3", RoundingMode::default()).result.unwrap().starts_with("-0.(3) is the fraction -1/3\n"));
+        assert!(Converter::explain_from_message("0xFF > 2", RoundingMode::default()).result.unwrap().ends_with("Result: 11111111"));
+        assert!(Converter::explain_from_message("0.5_10 > f32", RoundingMode::default()).error_kind == Some(ValidErrorKind::InvalidCondition));
+        assert!(Converter::explain_from_message("5_10 > 2 i8", RoundingMode::default()).error_kind == Some(ValidErrorKind::InvalidCondition));
+        assert!(Converter::explain_from_message("1234567890123456789_10 > 2", RoundingMode::default()).result.unwrap().ends_with(
+            "Result: 1000100100010000100001111010001111101111010011000000100010101"
+        ));
+        let long = format!("{}_10 > 2", "9".repeat(100));
+        assert!(Converter::explain_from_message(&long, RoundingMode::default()).error_kind == Some(ValidErrorKind::ExplanationTooLong));
+        assert!(Converter::explain_from_message("12_2 > 10", RoundingMode::default()).error_kind == Some(ValidErrorKind::InvalidInitialNumber));
+    }
+
+    #[test]
+    fn explain_from_message_2_() {
+        assert!(Converter::explain_from_message("0.1_10 > 2", RoundingMode::default()).result.unwrap().ends_with(
+            "Whole parts from the top down: 00011\n\
+            The fraction 0.2 comes up again, so the last 4 digits repeat\n\
+            \n\
+            Result: 0.0(0011)\n\
+            Pre-period length: 1, period length: 4"
+        ));
+        assert!(Converter::explain_from_message("0.1_10 > 3_2", RoundingMode::HalfUp).result.unwrap().ends_with(
+            "The digits go on, so they are cut after 3 digits (rounding mode: halfup)\n\nResult: 0.001\nRounded up"
+        ));
+        assert!(Converter::explain_from_message("0.1_10 > 3_2 ceiling", RoundingMode::Floor).result.unwrap().ends_with(
+            "(rounding mode: ceiling)\n\nResult: 0.001\nRounded up"
+        ));
+        assert!(Converter::explain_from_message("0.996_10 > 2_10 ceiling", RoundingMode::default()).result.unwrap().ends_with(
+            "Remainders from the bottom up: 1\n\n\
+            Fractional part, repeated multiplication by 10:\n\
+            Fraction | × 10 | Digit\n\
+            ---------+------+------\n\
+            0.996    | 9.96 | 9\n\
+            0.96     | 9.6  | 9\n\
+            Whole parts from the top down: 99\n\
+            The digits go on, so they are cut after 2 digits (rounding mode: ceiling)\n\
+            \n\
+            Result: 1\nRounded up"
+        ));
+        assert!(Converter::explain_from_message("1.01p+5_2 > 10", RoundingMode::default()).error_kind == Some(ValidErrorKind::InvalidCondition));
+    }
+
+    #[test]
+    fn convert_from_message_22_() {
+        assert_eq!(Converter::convert_from_message("(FF_16 + 1011_2) * 7_8 > 10").result.unwrap(), "1862");
+        assert_eq!(Converter::convert_from_message("2_10 ^ 3_10 ^ 2_10 > 10").result.unwrap(), "512");
+        assert_eq!(Converter::convert_from_message("- 2_10 ** 2_10 > 10").result.unwrap(), "-4");
+        assert_eq!(Converter::convert_from_message("-2_10 ** 2_10 > 10").result.unwrap(), "-4");
+        assert_eq!(Converter::convert_from_message("1_10+2_10 > 10").result.unwrap(), "3");
+        assert_eq!(Converter::convert_from_message("(FF_16) > 10").result.unwrap(), "255");
+        assert_eq!(Converter::convert_from_message("(FF_16+1011_2)*7_8 > 10").result.unwrap(), "1862");
+        assert_eq!(Converter::convert_from_message("2_10^-3_10 > 2").result.unwrap(), "0.001");
+        assert_eq!(Converter::convert_from_message("1e-3_10+1_10 > 10").result.unwrap(), "1.001");
+        assert_eq!(Converter::convert_from_message("7_10 // -2_10 > 10").result.unwrap(), "-4");
+        assert_eq!(Converter::convert_from_message("7_10 % -2_10 > 10").result.unwrap(), "-1");
+        assert_eq!(Converter::convert_from_message("-(0xFF - XIV) > 16").result.unwrap(), "-F1");
+        assert_eq!(Converter::convert_from_message("1_10 / 3_10 > 3").result.unwrap(), "0.1");
+        assert_eq!(Converter::convert_from_message("2_10 ^ -3_10 > 2").result.unwrap(), "0.001");
+        assert_eq!(Converter::convert_from_message("1_10 / 3_10 > 2_10 halfeven").result.unwrap(), "0.33\nRounded down");
+        assert_eq!(Converter::convert_from_message("1+i_10 * 1-i_10 > 2i").result.unwrap(), "2");
+        assert_eq!(Converter::convert_from_message("D/_64 > 10").result.unwrap(), "255");
+        assert_eq!(Converter::convert_from_message("A+B_64 > 10").result.unwrap(), "3969");
+        assert_eq!(Converter::convert_from_message("+*_{+*} > 10").result.unwrap(), "1");
+        assert_eq!(Converter::convert_from_message("1-1_bal3 > 10").result.unwrap(), "7");
+        assert_eq!(Converter::convert_from_message("A+Bi_16 > 2i").result.unwrap(), "113222.2");
+        assert!(Converter::convert_from_message("(1_10 + 2_10 > 10").error_kind == Some(ValidErrorKind::InvalidExpression));
+        assert!(Converter::convert_from_message("1_10 + * 2_10 > 10").error_kind == Some(ValidErrorKind::InvalidExpression));
+        assert!(Converter::convert_from_message("1_10+*2_10 > 10").error_kind == Some(ValidErrorKind::InvalidExpression));
+        assert!(Converter::convert_from_message("2_10 ^ 0.5_10 > 10").error_kind == Some(ValidErrorKind::InvalidExpression));
+        assert!(Converter::convert_from_message("1+i_10 % 2_10 > 2i").error_kind == Some(ValidErrorKind::InvalidExpression));
+        assert!(Converter::convert_from_message("1_10 / (1_10 - 1_10) > 10").error_kind == Some(ValidErrorKind::DivisionByZero));
+        assert!(Converter::convert_from_message("2_10 ^ 100000_10 > 10").error_kind == Some(ValidErrorKind::NumberTooLong));
+        assert!(Converter::convert_from_message("2_10^4096_10*2_10^4096_10 > 10").result.is_some());
+        let chain = format!("2_10^4096_10{} > 10", "*2_10^4096_10".repeat(8));
+        assert!(Converter::convert_from_message(&chain).error_kind == Some(ValidErrorKind::InvalidExpression));
+        let chain = format!("1_10/3_10^4096_10{} > 10", "/3_10^4096_10".repeat(8));
+        assert!(Converter::convert_from_message(&chain).error_kind == Some(ValidErrorKind::InvalidExpression));
+        assert!(Converter::convert_from_message("2_10 + 3 > 10").error_kind == Some(ValidErrorKind::InvalidInitialNumber));
+        assert!(Converter::convert_from_message("2_10 + 3_10 > 10 nearest").error_kind == Some(ValidErrorKind::InvalidCondition));
+    }
+
+    #[test]
+    fn convert_from_message_23_() {
+        assert_eq!(Converter::convert_from_message("~0x0F_16 u8 > 2").result.unwrap(), "11110000\n\n  0000 1111  0x0F_16\n= 1111 0000");
+        assert_eq!(
+            Converter::convert_from_message("0xF0 & 0x3C > 2").result.unwrap(),
+            "110000\n\n  1111 0000  0xF0\n  0011 1100  0x3C\n= 0011 0000"
+        );
+        assert_eq!(
+            Converter::convert_from_message("0xF0 | 0x0F i8 > 10").result.unwrap(),
+            "-1\n\n  1111 0000  0xF0\n  0000 1111  0x0F\n= 1111 1111"
+        );
+        assert_eq!(Converter::convert_from_message("0x81 rol 1_10 > 16 u8").result.unwrap(), "3\n\n  1000 0001  0x81\n= 0000 0011");
+        assert_eq!(Converter::convert_from_message("0x81 ror 9_10 u8 > 16").result.unwrap(), "C0\n\n  1000 0001  0x81\n= 1100 0000");
+        assert_eq!(Converter::convert_from_message("rev 0x01 u8 > 2").result.unwrap(), "10000000\n\n  0000 0001  0x01\n= 1000 0000");
+        assert_eq!(Converter::convert_from_message("~5_10 > 10").result.unwrap(), "-6\n\n  0101  5_10\n= 1010");
+        assert_eq!(Converter::convert_from_message("-8_10 >> 1_10 > 10").result.unwrap(), "-4\n\n  1000  -8_10\n= 1100");
+        assert_eq!(Converter::convert_from_message("-8_10>>1_10 u8 > 10").result.unwrap(), "124\n\n  1111 1000  -8_10\n= 0111 1100");
+        assert_eq!(Converter::convert_from_message("1_10 << 4_10 xor 3_10 > 2").result.unwrap(), "10011\n\n  0 0001  1_10\n  0 0011  3_10\n= 1 0011");
+        assert_eq!(Converter::convert_from_message("200_10 + 100_10 u8 > 10").result.unwrap(), "44");
+        assert!(Converter::convert_from_message("5_10 rol 1_10 > 2").error_kind == Some(ValidErrorKind::MissingWidth));
+        assert!(Converter::convert_from_message("rev 5_10 > 2").error_kind == Some(ValidErrorKind::MissingWidth));
+        assert!(Converter::convert_from_message("0x1FF & 1_10 u8 > 2").error_kind == Some(ValidErrorKind::Overflow));
+        assert!(Converter::convert_from_message("0.5_10 & 1_10 > 2").error_kind == Some(ValidErrorKind::UnrepresentableNumber));
+        assert!(Converter::convert_from_message("1_10 << -1_10 > 2").error_kind == Some(ValidErrorKind::InvalidExpression));
+        assert!(Converter::convert_from_message("1_10<<16000_10>>16000_10 > 10").result.is_some());
+        let chain = format!("1_10{} > 10", "<<16384_10".repeat(8));
+        assert!(Converter::convert_from_message(&chain).error_kind == Some(ValidErrorKind::InvalidExpression));
+    }
+}
+
+
+mod expression_test {
+    use crate::expression;
+    use crate::expression::{Expression, Operator, Token};
+
+    #[test]
+    fn tokenize_1_() {
+        assert_eq!(
+            expression::tokenize("-((FF_16 + 0.(3)_10) * 7_8)"),
+            vec![
+                Token::Operator(Operator::Subtract),
+                Token::OpenParen,
+                Token::OpenParen,
+                Token::Number("FF_16"),
+                Token::Operator(Operator::Add),
+                Token::Number("0.(3)_10"),
+                Token::CloseParen,
+                Token::Operator(Operator::Multiply),
+                Token::Number("7_8"),
+                Token::CloseParen,
+            ]
+        );
+        assert_eq!(expression::tokenize("1_2 // 1_2"), vec![Token::Number("1_2"), Token::Operator(Operator::IntegerDivide), Token::Number("1_2")]);
+    }
+
+    #[test]
+    fn tokenize_2_() {
+        assert_eq!(
+            expression::tokenize("-1e-3_10**(11101_-1+i-3+4i_10)//1^3_36"),
+            vec![
+                Token::Operator(Operator::Subtract),
+                Token::Number("1e-3_10"),
+                Token::Operator(Operator::Power),
+                Token::OpenParen,
+                Token::Number("11101_-1+i"),
+                Token::Operator(Operator::Subtract),
+                Token::Number("3+4i_10"),
+                Token::CloseParen,
+                Token::Operator(Operator::IntegerDivide),
+                Token::Number("1^3_36"),
+            ]
+        );
+        assert_eq!(
+            expression::tokenize("rev(0.(3)_{01}<<0x0F)xor~1_[24,60]"),
+            vec![
+                Token::Reverse,
+                Token::OpenParen,
+                Token::Number("0.(3)_{01}"),
+                Token::Operator(Operator::ShiftLeft),
+                Token::Number("0x0F"),
+                Token::CloseParen,
+                Token::Operator(Operator::Xor),
+                Token::Not,
+                Token::Number("1_[24,60]"),
+            ]
+        );
+        assert!(expression::is_expression("(FF_16)"));
+        assert!(expression::is_expression("1_10+*2_10"));
+        assert!(!expression::is_expression("-1.01p+5_2"));
+        assert!(!expression::is_expression("(3).1_10"));
+    }
+
+    #[test]
+    fn parse_1_() {
+        let number = |number| Box::new(Expression::Number(number));
+
+        assert_eq!(
+            expression::parse("1_10 - 2_10 * 3_10 % 4_10").ok(),
+            Some(Expression::Binary(
+                Operator::Subtract,
+                number("1_10"),
+                Box::new(Expression::Binary(
+                    Operator::Modulo,
+                    Box::new(Expression::Binary(Operator::Multiply, number("2_10"), number("3_10"))),
+                    number("4_10"),
+                )),
+            ))
+        );
+        assert_eq!(
+            expression::parse("- 2_10 ^ - 3_10").ok(),
+            Some(Expression::Negation(Box::new(Expression::Binary(
+                Operator::Power,
+                number("2_10"),
+                Box::new(Expression::Negation(number("3_10"))),
+            ))))
+        );
+        assert_eq!(
+            expression::parse("-2_10**2_10").ok(),
+            Some(Expression::Negation(Box::new(Expression::Binary(Operator::Power, number("2_10"), number("2_10")))))
+        );
+        assert!(expression::parse("1_10 2_10").is_err());
+        assert!(expression::parse("(1_10))").is_err());
+        assert!(expression::parse("").is_err());
+    }
+
+    #[test]
+    fn parse_2_() {
+        let number = |number| Box::new(Expression::Number(number));
+
+        assert_eq!(
+            expression::parse("~0x0F | 1_10 << 2_10 & 3_10").ok(),
+            Some(Expression::Binary(
+                Operator::Or,
+                Box::new(Expression::Not(number("0x0F"))),
+                Box::new(Expression::Binary(
+                    Operator::And,
+                    Box::new(Expression::Binary(Operator::ShiftLeft, number("1_10"), number("2_10"))),
+                    number("3_10"),
+                )),
+            ))
+        );
+        assert_eq!(expression::parse("rev 1_2 rol 1_2").ok().map(|tree| tree.bit_operands()), Some(vec![(false, "1_2")]));
+        assert!(expression::parse("1_2 ~ 1_2").is_err());
     }
 }